@@ -1,5 +1,32 @@
-use pocketpoker::run_console_game;
+use pocketpoker::score::Paytable;
+use pocketpoker::{run_console_game, run_stats};
+
+const USAGE: &str =
+    "usage: pocketpoker [--paytable NAME_OR_FILE] | stats [--exhaustive | --hands N] | paytables";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    run_console_game()
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match args[..] {
+        [] => run_console_game(Paytable::classic()),
+        ["--paytable", name] => {
+            // a built-in machine, or else a paytable file
+            let paytable = match Paytable::preset(name) {
+                Some(paytable) => paytable,
+                None => Paytable::load(name)?,
+            };
+            run_console_game(paytable)
+        }
+        ["paytables"] => {
+            for table in Paytable::presets() {
+                println!("{}", table);
+            }
+            Ok(())
+        }
+        ["stats"] => run_stats(false, 100_000),
+        ["stats", "--exhaustive"] => run_stats(true, 0),
+        ["stats", "--hands", hands] => run_stats(false, hands.parse()?),
+        _ => Err(USAGE.into()),
+    }
 }
@@ -4,14 +4,29 @@
 const SUIT_CHAR_SOLID: [char; 4] = ['♠', '♥', '♣', '♦'];
 // const SUIT_CHAR_MIXED: [char; 4] = ['♤', '♥', '♧', '♦'];
 const SUIT_CHAR: [char; 4] = SUIT_CHAR_SOLID;
+const SUIT_LETTER: [char; 4] = ['s', 'h', 'c', 'd'];
 
 // const RED_SUIT_STYLE: console::Style = console::Style::new().red();
 
-use num_enum::IntoPrimitive;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::combinations::{combinations, into_combinations, Combinations};
 
 use enum_iterator::Sequence;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, Sequence)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    IntoPrimitive,
+    TryFromPrimitive,
+    Sequence,
+)]
 #[repr(usize)]
 pub enum Rank {
     Two = 2,
@@ -27,6 +42,8 @@ pub enum Rank {
     Queen,
     King,
     Ace,
+    // jokers have no real rank, they sort above everything else
+    Joker,
 }
 
 impl Rank {
@@ -42,13 +59,57 @@ impl Rank {
     fn to_int_aces_high(&self) -> usize {
         (*self).into()
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Two => "Two",
+            Three => "Three",
+            Four => "Four",
+            Five => "Five",
+            Six => "Six",
+            Seven => "Seven",
+            Eight => "Eight",
+            Nine => "Nine",
+            Ten => "Ten",
+            Jack => "Jack",
+            Queen => "Queen",
+            King => "King",
+            Ace => "Ace",
+            Joker => "Joker",
+        }
+    }
+
+    pub fn plural(&self) -> &'static str {
+        match self {
+            Two => "Twos",
+            Three => "Threes",
+            Four => "Fours",
+            Five => "Fives",
+            Six => "Sixes",
+            Seven => "Sevens",
+            Eight => "Eights",
+            Nine => "Nines",
+            Ten => "Tens",
+            Jack => "Jacks",
+            Queen => "Queens",
+            King => "Kings",
+            Ace => "Aces",
+            Joker => "Jokers",
+        }
+    }
+
+    // the plain-text form used in card codes like `Th`
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Joker => "Jk",
+            _ => RANK_SYMBOL[self.to_int() - 1],
+        }
+    }
 }
 
-/*
-const RANK_SYMBOL: [char; 13] = [
-    'A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K',
+const RANK_SYMBOL: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K",
 ];
-*/
 
 const RANK_SYMBOL_WIDE: [&'static str; 13] = [
     " A", " 2", " 3", " 4", " 5", " 6", " 7", " 8", " 9", "10", " J", " Q", " K",
@@ -56,11 +117,52 @@ const RANK_SYMBOL_WIDE: [&'static str; 13] = [
 
 impl std::fmt::Display for Rank {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", RANK_SYMBOL_WIDE[self.to_int() - 1])
+        match self {
+            Rank::Joker => write!(f, "JK"),
+            _ => write!(f, "{}", RANK_SYMBOL_WIDE[self.to_int() - 1]),
+        }
+    }
+}
+
+/// accepts `A K Q J T 9 8 7 6 5 4 3 2` in either case, `10` for tens and `Jk` for the joker
+impl std::str::FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Rank, ParseCardError> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "A" => Ace,
+            "K" => King,
+            "Q" => Queen,
+            "J" => Jack,
+            "T" | "10" => Ten,
+            "9" => Nine,
+            "8" => Eight,
+            "7" => Seven,
+            "6" => Six,
+            "5" => Five,
+            "4" => Four,
+            "3" => Three,
+            "2" => Two,
+            "JK" => Joker,
+            _ => return Err(ParseCardError::InvalidRank(s.into())),
+        })
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Sequence)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    IntoPrimitive,
+    TryFromPrimitive,
+    Sequence,
+)]
+#[repr(usize)]
 pub enum Suit {
     Spade = 0,
     Heart,
@@ -68,24 +170,103 @@ pub enum Suit {
     Diamond,
 }
 
+impl Suit {
+    // the lowercase letter used in card codes like `Th`
+    pub fn letter(&self) -> char {
+        SUIT_LETTER[*self as usize]
+    }
+}
+
 impl std::fmt::Display for Suit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", SUIT_CHAR[*self as usize])
     }
 }
 
+/// accepts `s h c d` in either case, or the glyphs in `SUIT_CHAR_SOLID`
+impl std::str::FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        let mut chars = s.chars();
+
+        Ok(match (chars.next(), chars.next()) {
+            (Some('s' | 'S' | '♠'), None) => Suit::Spade,
+            (Some('h' | 'H' | '♥'), None) => Suit::Heart,
+            (Some('c' | 'C' | '♣'), None) => Suit::Club,
+            (Some('d' | 'D' | '♦'), None) => Suit::Diamond,
+            _ => return Err(ParseCardError::InvalidSuit(s.into())),
+        })
+    }
+}
+
 use Rank::*;
 
-const ACE_HIGH_STRAIGHT: [&Rank; 5] = [&Ten, &Jack, &Queen, &King, &Ace];
+// every rank except the joker, two to ace
+fn natural_ranks() -> impl Iterator<Item = Rank> {
+    enum_iterator::all::<Rank>().filter(|&rank| rank != Joker)
+}
 
 const FIVE_HIGH_STRAIGHT: [&Rank; 5] = [&Two, &Three, &Four, &Five, &Ace];
 
-#[derive(Clone, Copy, Eq)]
+// two cards are equal only if both rank and suit match. the derived ordering
+// sorts by rank first, so use `cmp_rank` when suits should not matter
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
 }
 
+impl Card {
+    // a joker's suit only tells it apart from other jokers (and picks its color)
+    pub const JOKER: Card = Card {
+        rank: Joker,
+        suit: Suit::Spade,
+    };
+
+    pub fn joker(suit: Suit) -> Card {
+        Card { rank: Joker, suit }
+    }
+
+    pub fn is_joker(&self) -> bool {
+        self.rank == Joker
+    }
+
+    pub fn cmp_rank(&self, other: &Card) -> std::cmp::Ordering {
+        self.rank.cmp(&other.rank)
+    }
+
+    // a short plain-text form that parses back to the same card, e.g. `Ah`, `Td` or `Jkh`.
+    // jokers keep their suit so that decks with several of them can be told apart
+    pub fn code(&self) -> String {
+        format!("{}{}", self.rank.symbol(), self.suit.letter())
+    }
+
+    // a number from 0 to 51, thirteen ranks (two to ace) per suit
+    // jokers come after those, from 52 to 55
+    pub fn to_index(&self) -> u8 {
+        let suit = usize::from(self.suit);
+
+        match self.rank {
+            Joker => (52 + suit) as u8,
+            rank => (suit * 13 + rank.to_int_aces_high() - 2) as u8,
+        }
+    }
+
+    pub fn from_index(index: u8) -> Option<Card> {
+        let index = index as usize;
+
+        if index >= 52 {
+            return Some(Card::joker(Suit::try_from(index - 52).ok()?));
+        }
+
+        Some(Card {
+            rank: Rank::try_from(index % 13 + 2).ok()?,
+            suit: Suit::try_from(index / 13).ok()?,
+        })
+    }
+}
+
 /// use {:+} to display suits with four colors
 impl std::fmt::Display for Card {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -113,6 +294,10 @@ impl std::fmt::Display for Card {
             }
         };
 
+        if self.is_joker() {
+            return write!(f, "{} ", style.apply_to("JKR"));
+        }
+
         write!(
             f,
             "{}{} ",
@@ -128,28 +313,104 @@ impl std::fmt::Debug for Card {
     }
 }
 
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(&other).unwrap()
+/// a rank followed by a suit, e.g. `As`, `10d`, `Tc` or `2♠`, or a joker: `Jk`, `Jkr` or `Joker`
+impl std::str::FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        if ["jk", "jkr", "joker"]
+            .iter()
+            .any(|name| s.eq_ignore_ascii_case(name))
+        {
+            return Ok(Card::JOKER);
+        }
+
+        let mut chars = s.chars();
+
+        let suit = match chars.next_back() {
+            Some(c) => c,
+            None => return Err(ParseCardError::InvalidCard(s.into())),
+        };
+
+        let rank = chars.as_str();
+
+        if rank.is_empty() {
+            return Err(ParseCardError::InvalidCard(s.into()));
+        }
+
+        let rank = rank
+            .parse::<Rank>()
+            .map_err(|_| ParseCardError::InvalidRank(s.into()))?;
+
+        let suit = suit
+            .to_string()
+            .parse::<Suit>()
+            .map_err(|_| ParseCardError::InvalidSuit(s.into()))?;
+
+        Ok(Card { rank, suit })
     }
 }
 
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.rank.cmp(&other.rank))
+// returns the first card that appears more than once
+pub fn find_duplicate(cards: &[Card]) -> Option<Card> {
+    cards
+        .iter()
+        .enumerate()
+        .find(|(i, card)| cards[..*i].contains(card))
+        .map(|(_, &card)| card)
+}
+
+/// parse any number of cards separated by whitespace or commas, rejecting duplicates
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut cards: Vec<Card> = vec![];
+
+    for token in s.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+
+        let card = token.parse::<Card>()?;
+
+        if cards.contains(&card) {
+            return Err(ParseCardError::DuplicateCard(token.into()));
+        }
+
+        cards.push(card);
     }
+
+    Ok(cards)
 }
 
-impl PartialEq for Card {
-    fn eq(&self, other: &Self) -> bool {
-        &self.rank == &other.rank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    // the token is too short to hold both a rank and a suit
+    InvalidCard(String),
+    InvalidRank(String),
+    InvalidSuit(String),
+    DuplicateCard(String),
+    WrongNumberOfCards(usize),
+}
+
+impl std::fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ParseCardError::*;
+
+        match self {
+            InvalidCard(token) => write!(f, "`{}` is not a card", token),
+            InvalidRank(token) => write!(f, "invalid rank in `{}`", token),
+            InvalidSuit(token) => write!(f, "invalid suit in `{}`", token),
+            DuplicateCard(token) => write!(f, "`{}` appears more than once", token),
+            WrongNumberOfCards(count) => write!(f, "expected 5 cards, found {}", count),
+        }
     }
 }
 
-// TODO: add a pointer to the "Top" of the deck and add a function to "draw" 5 cards
-// that is, move the pointer forward 5 and return a slice of length 5
+impl std::error::Error for ParseCardError {}
+
 pub struct Deck {
     deck: Vec<Card>,
+    // the "top" of the deck: every card before this index has already been dealt
+    top: usize,
 }
 
 impl Deck {
@@ -157,24 +418,65 @@ impl Deck {
         let mut deck = vec![];
 
         for suit in enum_iterator::all::<Suit>() {
-            for rank in enum_iterator::all::<Rank>() {
+            for rank in natural_ranks() {
                 deck.push(Card { rank, suit });
             }
         }
 
-        Deck { deck }
+        Deck { deck, top: 0 }
+    }
+
+    // a standard deck plus up to four jokers, each of a different suit
+    pub fn with_jokers(count: usize) -> Deck {
+        assert!(count <= 4, "Deck::with_jokers: at most four jokers");
+
+        let mut deck = Deck::new();
+
+        deck.deck
+            .extend(enum_iterator::all::<Suit>().take(count).map(Card::joker));
+
+        deck
+    }
+
+    // several standard decks shuffled together, as in a casino shoe
+    pub fn shoe(decks: usize) -> Deck {
+        let single = Deck::new();
+
+        Deck::from_cards(single.deck.repeat(decks))
+    }
+
+    // a standard deck with every rank below `lowest` taken out, so `Six` gives a short deck
+    // and `Seven` gives a piquet deck
+    pub fn stripped(lowest: Rank) -> Deck {
+        let mut deck = Deck::new();
+
+        deck.deck.retain(|card| card.rank >= lowest);
+
+        deck
+    }
+
+    // a deck of any cards in any order, the first card is on top
+    pub fn from_cards(cards: Vec<Card>) -> Deck {
+        Deck {
+            deck: cards,
+            top: 0,
+        }
     }
 
+    // total number of cards, dealt or not
     pub fn len(&self) -> usize {
         self.deck.len()
     }
 
-    pub fn index(&self, index: usize) -> &Card {
-        &self.deck[index]
+    pub fn is_empty(&self) -> bool {
+        self.deck.is_empty()
     }
 
     // Fisher-Yates shuffle
+    // this gathers up every dealt card first, so the whole deck is shuffled
     pub fn shuffle(&mut self, prng: &mut prng::Prng64) {
+        self.reset();
+
         let deck = &mut self.deck;
         for i in (1..deck.len()).rev() {
             let j = prng.next().unwrap() as usize % (i + 1);
@@ -182,19 +484,110 @@ impl Deck {
         }
     }
 
+    // move the top back to the start without changing the order of the cards
+    pub fn reset(&mut self) {
+        self.top = 0;
+    }
+
+    // deal `count` cards from the top, or nothing if there aren't enough left
+    pub fn draw(&mut self, count: usize) -> Option<&[Card]> {
+        let start = self.top;
+        let end = start.checked_add(count)?;
+
+        if end > self.deck.len() {
+            return None;
+        }
+
+        self.top = end;
+
+        Some(&self.deck[start..end])
+    }
+
+    // deal one card face down
+    pub fn burn(&mut self) -> Option<Card> {
+        self.draw(1).map(|cards| cards[0])
+    }
+
+    // look at the next `count` cards without dealing them
+    pub fn peek(&self, count: usize) -> Option<&[Card]> {
+        self.remaining().get(..count)
+    }
+
+    // cards that have not been dealt yet, next card first
+    pub fn remaining(&self) -> &[Card] {
+        &self.deck[self.top..]
+    }
+
+    // cards that have already been dealt (or burned), in the order they came off the deck
+    pub fn dealt(&self) -> &[Card] {
+        &self.deck[..self.top]
+    }
+
+    // every way to deal `K` of the remaining cards, e.g. `deck.combinations::<5>()` walks all
+    // 2,598,960 poker hands in a fresh deck
+    pub fn combinations<const K: usize>(&self) -> Combinations<'_, Card, K> {
+        combinations(self.remaining())
+    }
+
+    // like `Deck::combinations`, but leaving out dead cards, e.g. ones already in someone's
+    // hand. like `Deck::remove`, each listed card only leaves out one copy
+    pub fn combinations_excluding<const K: usize>(
+        &self,
+        dead: &[Card],
+    ) -> Combinations<'static, Card, K> {
+        let mut live = self.remaining().to_vec();
+
+        for card in dead {
+            if let Some(i) = live.iter().position(|c| c == card) {
+                live.remove(i);
+            }
+        }
+
+        into_combinations(live)
+    }
+
+    // take dead cards out of the deck entirely, returns how many were found
+    // if more than one copy of a card is in the deck, each listed card only removes one
+    pub fn remove(&mut self, cards: &[Card]) -> usize {
+        let mut removed = 0;
+
+        for card in cards {
+            if let Some(index) = self.deck.iter().position(|other| other == card) {
+                self.deck.remove(index);
+
+                if index < self.top {
+                    self.top -= 1;
+                }
+
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
     // return a hand made from a reference to five cards
     // TODO: actually make a version of Hand that uses references
     // then this function can instead make that
     pub fn _peek_poker_hand(&self, offset: usize, length: usize) -> Option<Hand> {
-        if self.deck.len() < length {
-            return None;
-        }
+        self.deck
+            .get(offset..offset.checked_add(length)?)
+            .map(Hand::new)
+    }
+}
 
-        Some(Hand::new(&self.deck[offset..(offset + length)]))
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
     }
+}
 
-    pub fn peek_five_cards(&self, offset: usize) -> &[Card] {
-        &self.deck[offset..(offset + 5)]
+// the card at that position, counting dealt cards too
+impl std::ops::Index<usize> for Deck {
+    type Output = Card;
+
+    fn index(&self, index: usize) -> &Card {
+        &self.deck[index]
     }
 }
 
@@ -210,6 +603,10 @@ pub struct Hand {
     runs: Vec<Vec<Card>>,
     kickers: Vec<Card>,
     ranking: HandRanking,
+    // where the ranking falls in the order of the rules the hand was made with
+    precedence: usize,
+    // each wild card in the hand, paired with the card it stands in for
+    substitutions: Vec<(Card, Card)>,
 }
 
 impl Hand {
@@ -221,13 +618,218 @@ impl Hand {
         &self.runs
     }
 
+    // the cards that aren't part of a run, lowest first
+    pub fn kickers(&self) -> &[Card] {
+        &self.kickers
+    }
+
     pub fn ranking(&self) -> &HandRanking {
         &self.ranking
     }
 
+    pub fn substitutions(&self) -> &[(Card, Card)] {
+        &self.substitutions
+    }
+
+    // a hand with no wild cards in it, e.g. to tell a natural royal from a wild one
+    pub fn is_natural(&self) -> bool {
+        self.substitutions.is_empty()
+    }
+
+    // jokers are wild, see `Hand::with_wilds` to make other ranks wild too
     pub fn new(hand: &[Card]) -> Hand {
+        Hand::with_rules(hand, RankingRules::standard_ref())
+    }
+
+    // each wild card is replaced by whichever card makes the best hand
+    pub fn with_wilds(hand: &[Card], wilds: &WildCards) -> Hand {
+        Hand::with_rules(
+            hand,
+            &RankingRules {
+                wilds: wilds.clone(),
+                ..RankingRules::standard()
+            },
+        )
+    }
+
+    // e.g. `Hand::with_rules(&cards, &RankingRules::short_deck())`. only compare hands
+    // made with the same rules
+    pub fn with_rules(hand: &[Card], rules: &RankingRules) -> Hand {
+        assert_eq!(hand.len(), rules.hand_size);
+
+        if let Some(card) = find_duplicate(hand) {
+            panic!("Hand::new: {:?}appears more than once", card);
+        }
+
+        Hand::evaluate_wild(hand, rules)
+    }
+
+    // like `Hand::with_rules`, but wild cards are replaced by whichever cards make the hand
+    // with the greatest `key`, e.g. what a paytable pays for it. ties go to the better hand
+    pub fn with_rules_by<K: Ord>(
+        hand: &[Card],
+        rules: &RankingRules,
+        key: impl Fn(&Hand) -> K,
+    ) -> Hand {
+        assert_eq!(hand.len(), rules.hand_size);
+
+        if let Some(card) = find_duplicate(hand) {
+            panic!("Hand::new: {:?}appears more than once", card);
+        }
+
+        Hand::evaluate_wild_by(hand, rules, key)
+    }
+
+    // like `Hand::new`, but for cards dealt from a shoe of several decks, so the same card
+    // can show up more than once. this is how five of a kind and flush five are made
+    pub fn from_shoe(hand: &[Card]) -> Hand {
         assert_eq!(hand.len(), 5);
 
+        Hand::evaluate_wild(hand, RankingRules::standard_ref())
+    }
+
+    // the best five-card hand that can be made from five or more cards,
+    // e.g. all seven cards in stud. ties go to the first hand found
+    pub fn best_of(cards: &[Card]) -> Hand {
+        Hand::best_of_with_rules(cards, RankingRules::standard_ref())
+    }
+
+    // like `Hand::best_of`, but the hands are as big as the rules say, e.g. the best
+    // three cards out of a bigger deal
+    pub fn best_of_with_rules(cards: &[Card], rules: &RankingRules) -> Hand {
+        assert!(
+            cards.len() >= rules.hand_size,
+            "Hand::best_of: fewer than {} cards",
+            rules.hand_size
+        );
+
+        if let Some(card) = find_duplicate(cards) {
+            panic!("Hand::best_of: {:?}appears more than once", card);
+        }
+
+        let mut best: Option<Hand> = None;
+
+        for picked in crate::combinations::choose(cards, rules.hand_size) {
+            let hand = Hand::evaluate_wild(&picked, rules);
+
+            best = match best {
+                Some(best) if hand.compare(&best) != std::cmp::Ordering::Greater => Some(best),
+                _ => Some(hand),
+            };
+        }
+
+        best.unwrap()
+    }
+
+    // the best five-card hand using any of the hole cards and the community board
+    pub fn best_of_hole_and_board(hole: &[Card], board: &[Card]) -> Hand {
+        Hand::best_of(&[hole, board].concat())
+    }
+
+    fn evaluate_wild(hand: &[Card], rules: &RankingRules) -> Hand {
+        Hand::evaluate_wild_by(hand, rules, |_| ())
+    }
+
+    fn evaluate_wild_by<K: Ord>(
+        hand: &[Card],
+        rules: &RankingRules,
+        key: impl Fn(&Hand) -> K,
+    ) -> Hand {
+        let (wild, natural): (Vec<Card>, Vec<Card>) =
+            hand.iter().partition(|card| rules.wilds.is_wild(card));
+
+        if wild.is_empty() {
+            return Hand::evaluate(hand, rules);
+        }
+
+        let ranks = natural_ranks().collect::<Vec<_>>();
+
+        // the only suit wild cards could take to make a flush
+        let flush_suit = match natural.first() {
+            None => Some(Suit::Spade),
+            Some(first) => natural
+                .iter()
+                .all(|card| card.suit == first.suit)
+                .then_some(first.suit),
+        };
+
+        let mut best: Option<(K, Hand)> = None;
+
+        let mut consider = |substitutes: Vec<Card>| {
+            let cards = [&natural[..], &substitutes[..]].concat();
+            let mut candidate = Hand::evaluate(&cards, rules);
+            candidate.hand = hand.to_vec();
+            candidate.substitutions = wild.iter().copied().zip(substitutes).collect();
+
+            let candidate_key = key(&candidate);
+
+            let is_better = match &best {
+                None => true,
+                Some((best_key, best)) => candidate_key
+                    .cmp(best_key)
+                    .then_with(|| candidate.compare(best))
+                    .is_gt(),
+            };
+
+            if is_better {
+                best = Some((candidate_key, candidate));
+            }
+        };
+
+        // the order of the wild cards doesn't matter, so only try each multiset of ranks once
+        let mut choice = vec![0; wild.len()];
+
+        'choices: loop {
+            // pick suits that aren't in the hand yet, so that the ranks are all that matter
+            let mut substitutes = vec![];
+            for &i in &choice {
+                let rank = ranks[i];
+                let suit = enum_iterator::all::<Suit>()
+                    .find(|&suit| {
+                        let card = Card { rank, suit };
+                        !natural.contains(&card) && !substitutes.contains(&card)
+                    })
+                    .unwrap_or(Suit::Spade);
+                substitutes.push(Card { rank, suit });
+            }
+            consider(substitutes);
+
+            if let Some(suit) = flush_suit {
+                let substitutes = choice
+                    .iter()
+                    .map(|&i| Card {
+                        rank: ranks[i],
+                        suit,
+                    })
+                    .collect::<Vec<_>>();
+
+                let cards = [&natural[..], &substitutes[..]].concat();
+                if find_duplicate(&cards).is_none() {
+                    consider(substitutes);
+                }
+            }
+
+            let mut i = choice.len();
+            loop {
+                if i == 0 {
+                    break 'choices;
+                }
+                i -= 1;
+
+                if choice[i] + 1 < ranks.len() {
+                    choice[i] += 1;
+                    for j in i + 1..choice.len() {
+                        choice[j] = choice[i];
+                    }
+                    break;
+                }
+            }
+        }
+
+        best.unwrap().1
+    }
+
+    fn evaluate(hand: &[Card], rules: &RankingRules) -> Hand {
         let mut copy = hand.to_vec();
 
         copy.sort();
@@ -276,26 +878,21 @@ impl Hand {
             .filter(|run| run.len() > 1)
             .collect::<Vec<_>>();
 
-        let is_straight = Hand::eval_is_straight(&sorted);
-
-        let is_five_high_straight =
-            sorted.iter().map(|card| &card.rank).collect::<Vec<_>>() == FIVE_HIGH_STRAIGHT;
-
-        if is_straight & is_five_high_straight {
-            // Fix order of "kicker" section for five-high straights
-            let ace = kickers.pop().unwrap();
-            kickers.insert(0, ace);
-            let kickers_ranks = kickers.iter().map(|card| &card.rank).collect::<Vec<_>>();
-            assert_eq!(kickers_ranks, [&Ace, &Two, &Three, &Four, &Five]);
+        // put the kickers of a straight in straight order, e.g. the ace first in a wheel
+        let straight = match runs.is_empty() {
+            true => Hand::straight_order(&kickers, rules),
+            false => None,
+        };
+        let is_straight = straight.is_some();
+        if let Some(straight) = straight {
+            kickers = straight;
         }
 
         let is_flush = Hand::eval_is_flush(&sorted);
 
-        let ranks = sorted.iter().map(|card| &card.rank).collect::<Vec<_>>();
-
-        let is_royal = ranks == ACE_HIGH_STRAIGHT;
+        let is_royal = is_straight && kickers.last().map(|card| card.rank) == Some(Ace);
 
-        let ranking = Self::rank_hand(&runs, is_straight, is_flush, is_royal);
+        let (ranking, precedence) = Self::rank_hand(&runs, is_straight, is_flush, is_royal, rules);
 
         Hand {
             // TODO: attempt to convert this struct to hold a reference instead
@@ -303,7 +900,71 @@ impl Hand {
             runs,
             kickers,
             ranking,
+            precedence,
+            substitutions: vec![],
+        }
+    }
+
+    // given cards of different ranks sorted aces high, the same cards lowest first
+    // if they make a straight under these rules
+    fn straight_order(sorted: &[Card], rules: &RankingRules) -> Option<Vec<Card>> {
+        let values = sorted
+            .iter()
+            .map(|card| card.rank.to_int_aces_high())
+            .collect::<Vec<_>>();
+
+        if values.windows(2).all(|pair| pair[1] == pair[0] + 1) {
+            return Some(sorted.to_vec());
+        }
+
+        // straights where the ace plays low, like the wheel
+        for straight in &rules.low_straights {
+            if straight.len() == sorted.len()
+                && straight
+                    .iter()
+                    .all(|&rank| sorted.iter().any(|card| card.rank == rank))
+            {
+                return Some(
+                    straight
+                        .iter()
+                        .map(|&rank| *sorted.iter().find(|card| card.rank == rank).unwrap())
+                        .collect(),
+                );
+            }
         }
+
+        // going around the corner, there's exactly one gap in the ranks, just below the
+        // lowest card of the straight
+        if rules.wraparound {
+            let n = values.len();
+            let gaps = (0..n)
+                .filter(|&i| (values[(i + 1) % n] + 13 - values[i]) % 13 != 1)
+                .collect::<Vec<_>>();
+
+            if let [gap] = gaps[..] {
+                let mut straight = sorted.to_vec();
+                straight.rotate_left((gap + 1) % n);
+                return Some(straight);
+            }
+        }
+
+        None
+    }
+
+    pub fn is_straight(&self) -> bool {
+        use HandRanking::*;
+
+        matches!(self.ranking, Straight | StraightFlush | RoyalFlush)
+    }
+
+    pub fn is_flush(&self) -> bool {
+        use HandRanking::*;
+
+        matches!(self.ranking, Flush | StraightFlush | RoyalFlush)
+    }
+
+    pub fn is_royal(&self) -> bool {
+        self.ranking == HandRanking::RoyalFlush
     }
 
     pub fn _high_rank(&self) -> Option<Rank> {
@@ -370,56 +1031,181 @@ impl Hand {
         )
     }
 
+    // every category the hand makes, and the one the rules rank highest wins, along with
+    // where it falls in their order
     fn rank_hand(
         runs: &Vec<Vec<Card>>,
         is_straight: bool,
         is_flush: bool,
         is_royal: bool,
-    ) -> HandRanking {
+        rules: &RankingRules,
+    ) -> (HandRanking, usize) {
         use HandRanking::*;
 
-        if let Some(ranking) = Self::runs_ranking(runs) {
-            return ranking;
+        let mut best = (HighCard, rules.precedence(HighCard).unwrap());
+
+        let mut consider = |ranking: HandRanking| {
+            if let Some(precedence) = rules.precedence(ranking) {
+                if precedence > best.1 {
+                    best = (ranking, precedence);
+                }
+            }
+        };
+
+        let runs_ranking = Self::runs_ranking(runs);
+        if let Some(ranking) = runs_ranking {
+            consider(ranking);
+        }
+
+        // with more than one deck, a flush can also have matching ranks
+        if is_flush {
+            consider(Flush);
+            if runs_ranking == Some(FiveOfAKind) {
+                consider(FlushFive);
+            }
         }
 
         if is_straight {
+            consider(Straight);
             if is_flush {
+                consider(StraightFlush);
                 if is_royal {
-                    return RoyalFlush;
+                    consider(RoyalFlush);
                 }
-                return StraightFlush;
             }
-            return Straight;
         }
 
-        if is_flush {
-            return Flush;
-        }
-
-        HighCard
+        best
     }
 
     pub fn compare(&self, other: &Hand) -> std::cmp::Ordering {
-        let order = self.ranking.cmp(&other.ranking);
+        let order = self.precedence.cmp(&other.precedence);
 
         match order {
             std::cmp::Ordering::Equal => (),
             _ => return order,
         }
 
-        // this is the way it should be
-        // it would work if i went through and changed the internal formats to be reversed
-        // (&self.runs, &self.kickers).cmp(&(&other.runs, &other.kickers))
+        // then the biggest run down to the smallest, then the highest kicker down.
+        // only ranks are compared, suits never break a tie
+        let ranks = |hand: &Hand| match hand.ranking {
+            // a flush from a shoe can have a pair in it, which counts for nothing,
+            // so every card is compared from the highest down
+            HandRanking::Flush | HandRanking::FlushFive => {
+                let mut ranks = hand
+                    .runs
+                    .iter()
+                    .flatten()
+                    .chain(&hand.kickers)
+                    .map(|card| card.rank)
+                    .collect::<Vec<_>>();
+                ranks.sort_by(|a, b| b.cmp(a));
+                (vec![], ranks)
+            }
+            _ => (
+                hand.runs
+                    .iter()
+                    .rev()
+                    .map(|run| run.iter().map(|card| card.rank).collect::<Vec<_>>())
+                    .collect::<Vec<_>>(),
+                hand.kickers
+                    .iter()
+                    .rev()
+                    .map(|card| card.rank)
+                    .collect::<Vec<_>>(),
+            ),
+        };
+
+        ranks(self).cmp(&ranks(other))
+    }
 
-        // this way is stupid but i'm gonna do it for now, because reversing them internally is too hard
-        (
-            &self.runs.iter().rev().collect::<Vec<_>>(),
-            &self.kickers.iter().rev().collect::<Vec<_>>(),
-        )
-            .cmp(&(
-                &other.runs.iter().rev().collect::<Vec<_>>(),
-                &other.kickers.iter().rev().collect::<Vec<_>>(),
-            ))
+    // e.g. "Two Pair, Kings and Sevens, Ace kicker"
+    pub fn describe(&self) -> String {
+        use HandRanking::*;
+
+        // most important first
+        let runs = self
+            .runs
+            .iter()
+            .rev()
+            .map(|run| run[0].rank)
+            .collect::<Vec<_>>();
+        let kickers = self
+            .kickers
+            .iter()
+            .rev()
+            .map(|card| card.rank)
+            .collect::<Vec<_>>();
+
+        // every rank from highest to lowest, for hands where all five cards count
+        let mut ranks = self
+            .runs
+            .iter()
+            .flatten()
+            .chain(&self.kickers)
+            .map(|card| card.rank)
+            .collect::<Vec<_>>();
+        ranks.sort_by(|a, b| b.cmp(a));
+
+        let kicker_phrase = |kickers: &[Rank]| {
+            let names = kickers.iter().map(Rank::name).collect::<Vec<_>>();
+            match names.len() {
+                // smaller hands can run out of kickers
+                0 => String::new(),
+                1 => format!(", {} kicker", names[0]),
+                _ => format!(", {} kickers", names.join("-")),
+            }
+        };
+
+        // straights keep the ace at the bottom of a wheel, so use the kicker order
+        let is_wheel = kickers.len() == 5 && kickers.first() == Some(&Five);
+
+        match self.ranking {
+            HighCard => format!(
+                "High Card, {}{}",
+                ranks[0].name(),
+                kicker_phrase(&ranks[1..])
+            ),
+            Pair => format!("Pair of {}{}", runs[0].plural(), kicker_phrase(&kickers)),
+            TwoPair => format!(
+                "Two Pair, {} and {}{}",
+                runs[0].plural(),
+                runs[1].plural(),
+                kicker_phrase(&kickers)
+            ),
+            ThreeOfAKind => format!(
+                "Three of a Kind, {}{}",
+                runs[0].plural(),
+                kicker_phrase(&kickers)
+            ),
+            Straight if is_wheel => "Five-high straight, the wheel".into(),
+            Straight => format!("{}-high straight", kickers[0].name()),
+            Flush => format!(
+                "{}-high flush, {}",
+                ranks[0].name(),
+                ranks[1..]
+                    .iter()
+                    .map(Rank::name)
+                    .collect::<Vec<_>>()
+                    .join("-")
+            ),
+            FullHouse => format!(
+                "Full House, {} full of {}",
+                runs[0].plural(),
+                runs[1].plural()
+            ),
+            FourOfAKind => format!(
+                "Four of a Kind, {}{}",
+                runs[0].plural(),
+                kicker_phrase(&kickers)
+            ),
+            StraightFlush if is_wheel => "Five-high straight flush, the steel wheel".into(),
+            StraightFlush => format!("{}-high straight flush", kickers[0].name()),
+            RoyalFlush if self.is_natural() => "Royal Flush".into(),
+            RoyalFlush => "Wild Royal Flush".into(),
+            FiveOfAKind => format!("Five of a Kind, {}", runs[0].plural()),
+            FlushFive => format!("Flush Five, {}", runs[0].plural()),
+        }
     }
 
     pub fn _display_hand_with_info(&self) -> String {
@@ -469,7 +1255,173 @@ impl std::fmt::Display for Hand {
     }
 }
 
+/// exactly five cards, see `parse_cards`
+impl std::str::FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Hand, ParseCardError> {
+        let cards = parse_cards(s)?;
+
+        if cards.len() != 5 {
+            return Err(ParseCardError::WrongNumberOfCards(cards.len()));
+        }
+
+        Ok(Hand::new(&cards))
+    }
+}
+
+// which cards can stand in for any other card. jokers are always wild
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WildCards {
+    ranks: Vec<Rank>,
+}
+
+impl WildCards {
+    // e.g. `WildCards::ranks(&[Rank::Two])` for deuces wild
+    pub fn ranks(ranks: &[Rank]) -> WildCards {
+        WildCards {
+            ranks: ranks.to_vec(),
+        }
+    }
+
+    pub fn is_wild(&self, card: &Card) -> bool {
+        card.is_joker() || self.ranks.contains(&card.rank)
+    }
+}
+
+// how hands are ranked, for games that don't use the usual five-card order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankingRules {
+    // how many cards make a hand
+    pub hand_size: usize,
+    // categories from worst to best, see `RankingRules::set_order`
+    order: Vec<HandRanking>,
+    // where each category falls in `order`, looked up by `HandRanking as usize`
+    precedences: [Option<usize>; HAND_RANKING_COUNT],
+    // straights where the ace plays low, lowest card first, e.g. ace-two-three-four-five
+    pub low_straights: Vec<Vec<Rank>>,
+    // whether a straight can go around the corner, like queen-king-ace-two-three
+    pub wraparound: bool,
+    pub wilds: WildCards,
+}
+
+impl RankingRules {
+    pub fn standard() -> RankingRules {
+        use HandRanking::*;
+
+        let mut rules = RankingRules {
+            hand_size: 5,
+            order: vec![],
+            precedences: [None; HAND_RANKING_COUNT],
+            low_straights: vec![vec![Ace, Two, Three, Four, Five]],
+            wraparound: false,
+            wilds: WildCards::default(),
+        };
+
+        rules.set_order(vec![
+            HighCard,
+            Pair,
+            TwoPair,
+            ThreeOfAKind,
+            Straight,
+            Flush,
+            FullHouse,
+            FourOfAKind,
+            StraightFlush,
+            RoyalFlush,
+            FiveOfAKind,
+            FlushFive,
+        ]);
+        rules
+    }
+
+    // the standard rules, without building them for every hand
+    fn standard_ref() -> &'static RankingRules {
+        static STANDARD: std::sync::OnceLock<RankingRules> = std::sync::OnceLock::new();
+
+        STANDARD.get_or_init(RankingRules::standard)
+    }
+
+    // six-plus hold'em, dealt from `Deck::stripped(Rank::Six)`. with fewer cards of each suit,
+    // a flush is harder to make than a full house, and the ace plays low under a six
+    pub fn short_deck() -> RankingRules {
+        use HandRanking::*;
+
+        let mut rules = RankingRules {
+            low_straights: vec![vec![Ace, Six, Seven, Eight, Nine]],
+            ..RankingRules::standard()
+        };
+
+        rules.set_order(vec![
+            HighCard,
+            Pair,
+            TwoPair,
+            ThreeOfAKind,
+            Straight,
+            FullHouse,
+            Flush,
+            FourOfAKind,
+            StraightFlush,
+            RoyalFlush,
+        ]);
+        rules
+    }
+
+    // three-card poker, where three of a kind and straights are harder to make than flushes
+    pub fn three_card() -> RankingRules {
+        use HandRanking::*;
+
+        let mut rules = RankingRules {
+            hand_size: 3,
+            low_straights: vec![vec![Ace, Two, Three]],
+            ..RankingRules::standard()
+        };
+
+        rules.set_order(vec![
+            HighCard,
+            Pair,
+            Flush,
+            Straight,
+            ThreeOfAKind,
+            StraightFlush,
+            RoyalFlush,
+        ]);
+        rules
+    }
+
+    pub fn order(&self) -> &[HandRanking] {
+        &self.order
+    }
+
+    // categories from worst to best. ones left out can't be made, but `HighCard` is required
+    pub fn set_order(&mut self, order: Vec<HandRanking>) {
+        assert!(
+            order.contains(&HandRanking::HighCard),
+            "RankingRules::set_order: `HighCard` is required"
+        );
+
+        self.precedences = [None; HAND_RANKING_COUNT];
+        for (precedence, &ranking) in order.iter().enumerate() {
+            self.precedences[ranking as usize] = Some(precedence);
+        }
+
+        self.order = order;
+    }
+
+    // where a category falls in the order, higher is better. `None` if it can't be made
+    pub fn precedence(&self, ranking: HandRanking) -> Option<usize> {
+        self.precedences[ranking as usize]
+    }
+}
+
+impl Default for RankingRules {
+    fn default() -> RankingRules {
+        RankingRules::standard()
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(usize)]
 pub enum HandRanking {
     HighCard = 0,
@@ -483,6 +1435,35 @@ pub enum HandRanking {
     StraightFlush,
     RoyalFlush,
     FiveOfAKind,
+    // five identical cards, only possible with more than one deck
+    FlushFive,
+}
+
+const HAND_RANKING_COUNT: usize = HandRanking::FlushFive as usize + 1;
+
+impl std::fmt::Display for HandRanking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use HandRanking::*;
+
+        write!(
+            f,
+            "{}",
+            match self {
+                HighCard => "High Card",
+                Pair => "Pair",
+                TwoPair => "Two Pair",
+                ThreeOfAKind => "Three of a Kind",
+                Straight => "Straight",
+                Flush => "Flush",
+                FullHouse => "Full House",
+                FourOfAKind => "Four of a Kind",
+                StraightFlush => "Straight Flush",
+                RoyalFlush => "Royal Flush",
+                FiveOfAKind => "Five of a Kind",
+                FlushFive => "Flush Five",
+            }
+        )
+    }
 }
 
 #[cfg(test)]
@@ -492,21 +1473,7 @@ mod tests {
 
     #[test]
     fn royal_flush() {
-        use Rank::*;
-        use Suit::*;
-
-        let hand = Hand::new(
-            &[
-                (Ten, Diamond),
-                (Jack, Diamond),
-                (Queen, Diamond),
-                (King, Diamond),
-                (Ace, Diamond),
-            ]
-            .into_iter()
-            .map(|(rank, suit)| Card { rank, suit })
-            .collect::<Vec<_>>()[..],
-        );
+        let hand = "Td Jd Qd Kd Ad".parse::<Hand>().unwrap();
 
         assert!(hand.is_straight());
         assert!(hand.is_flush());
@@ -515,21 +1482,7 @@ mod tests {
 
     #[test]
     fn ace_high_straight() {
-        use Rank::*;
-        use Suit::*;
-
-        let hand = Hand::new(
-            &[
-                (Ten, Heart),
-                (Jack, Spade),
-                (Queen, Spade),
-                (King, Spade),
-                (Ace, Spade),
-            ]
-            .into_iter()
-            .map(|(rank, suit)| Card { rank, suit })
-            .collect::<Vec<_>>()[..],
-        );
+        let hand = "Th Js Qs Ks As".parse::<Hand>().unwrap();
 
         use HandRanking::*;
 
@@ -543,21 +1496,7 @@ mod tests {
 
     #[test]
     fn five_high_straight() {
-        use Rank::*;
-        use Suit::*;
-
-        let hand = Hand::new(
-            &[
-                (Ace, Heart),
-                (Two, Spade),
-                (Three, Spade),
-                (Four, Spade),
-                (Five, Spade),
-            ]
-            .into_iter()
-            .map(|(rank, suit)| Card { rank, suit })
-            .collect::<Vec<_>>()[..],
-        );
+        let hand = "Ah 2s 3s 4s 5s".parse::<Hand>().unwrap();
 
         let mut copy = hand.hand.clone();
 
@@ -594,36 +1533,377 @@ mod tests {
 
     #[test]
     fn five_high_straight_compare() {
+        let hand = "Ah 2s 3s 4s 5s".parse::<Hand>().unwrap();
+
+        let other_hand = "2s 3s 4s 5s 6h".parse::<Hand>().unwrap();
+
+        assert!(hand < other_hand);
+        assert_eq!(hand.compare(&other_hand), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn parse_ascii_and_glyphs() {
         use Rank::*;
         use Suit::*;
 
-        let hand = Hand::new(
-            &[
-                (Ace, Heart),
-                (Two, Spade),
-                (Three, Spade),
-                (Four, Spade),
-                (Five, Spade),
-            ]
-            .into_iter()
-            .map(|(rank, suit)| Card { rank, suit })
-            .collect::<Vec<_>>()[..],
+        let cards = parse_cards("As kh 10d Tc 2♠, 9♦").unwrap();
+
+        let expected = [
+            (Ace, Spade),
+            (King, Heart),
+            (Ten, Diamond),
+            (Ten, Club),
+            (Two, Spade),
+            (Nine, Diamond),
+        ];
+
+        assert_eq!(cards.len(), expected.len());
+        for (card, (rank, suit)) in cards.iter().zip(expected) {
+            assert!(card.rank == rank && card.suit == suit);
+        }
+    }
+
+    #[test]
+    fn parse_errors_name_the_token() {
+        use ParseCardError::*;
+
+        assert_eq!(parse_cards("As Xh"), Err(InvalidRank("Xh".into())));
+        assert_eq!(parse_cards("As Kx"), Err(InvalidSuit("Kx".into())));
+        assert_eq!(parse_cards("As K"), Err(InvalidCard("K".into())));
+        assert_eq!(parse_cards("As Kh as"), Err(DuplicateCard("as".into())));
+        assert_eq!(
+            "As Kh Qh".parse::<Hand>().err(),
+            Some(WrongNumberOfCards(3))
         );
+    }
 
-        let other_hand = Hand::new(
-            &[
-                (Two, Spade),
-                (Three, Spade),
-                (Four, Spade),
-                (Five, Spade),
-                (Six, Heart),
-            ]
-            .into_iter()
-            .map(|(rank, suit)| Card { rank, suit })
-            .collect::<Vec<_>>()[..],
+    #[test]
+    fn card_identity() {
+        use std::collections::HashSet;
+
+        let cards = parse_cards("As Ah Kd").unwrap();
+
+        assert_ne!(cards[0], cards[1]);
+        assert_eq!(cards[0].cmp_rank(&cards[1]), std::cmp::Ordering::Equal);
+        assert_eq!(cards[2].cmp_rank(&cards[0]), std::cmp::Ordering::Less);
+
+        let set = cards
+            .iter()
+            .chain(cards.iter())
+            .copied()
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 3);
+
+        assert_eq!(find_duplicate(&cards), None);
+        let mut doubled = cards.clone();
+        doubled.push(cards[1]);
+        assert_eq!(find_duplicate(&doubled), Some(cards[1]));
+    }
+
+    #[test]
+    fn suits_do_not_break_ties() {
+        let hand = "Ks Kh 9c 5d 2s".parse::<Hand>().unwrap();
+        let other_hand = "Kc Kd 9s 5h 2c".parse::<Hand>().unwrap();
+
+        assert_eq!(hand.compare(&other_hand), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duplicate_cards_rejected() {
+        let cards = parse_cards("As Kh Qd Jc").unwrap();
+        Hand::new(&[cards[0], cards[1], cards[2], cards[3], cards[0]]);
+    }
+
+    #[test]
+    fn card_index_round_trip() {
+        let deck = Deck::new();
+
+        for i in 0..deck.len() {
+            let card = deck[i];
+            assert_eq!(Card::from_index(card.to_index()), Some(card));
+        }
+
+        assert_eq!("2s".parse::<Card>().unwrap().to_index(), 0);
+        assert_eq!("Ad".parse::<Card>().unwrap().to_index(), 51);
+        assert_eq!(Card::JOKER.to_index(), 52);
+        assert_eq!(Card::from_index(52), Some(Card::JOKER));
+        assert_eq!(Card::from_index(56), None);
+    }
+
+    #[test]
+    fn draw_from_the_top() {
+        let mut deck = Deck::new();
+
+        let first = deck.draw(5).unwrap().to_vec();
+        assert_eq!(first, deck.dealt());
+        assert_eq!(deck.remaining().len(), 47);
+
+        let burned = deck.burn().unwrap();
+        let next = deck.peek(2).unwrap().to_vec();
+        assert_eq!(deck.draw(2).unwrap(), &next[..]);
+        assert_eq!(deck.dealt()[5], burned);
+        assert_eq!(deck.remaining().len(), 44);
+
+        assert_eq!(deck.draw(45), None);
+        assert_eq!(deck.remaining().len(), 44);
+        assert_eq!(deck.draw(44).unwrap().len(), 44);
+        assert_eq!(deck.burn(), None);
+        assert_eq!(deck.peek(1), None);
+
+        deck.reset();
+        assert_eq!(deck.draw(5).unwrap(), &first[..]);
+    }
+
+    #[test]
+    fn remove_dead_cards() {
+        let mut deck = Deck::new();
+        let dealt = deck.draw(3).unwrap().to_vec();
+
+        let dead = parse_cards("As Kh").unwrap();
+        let removed = deck.remove(&[dealt[0], dead[0], dead[1], dead[1]]);
+
+        assert_eq!(removed, 3);
+        assert_eq!(deck.len(), 49);
+        assert_eq!(deck.dealt(), &dealt[1..]);
+        assert!(!deck.remaining().contains(&dead[0]));
+        assert!(!deck.remaining().contains(&dead[1]));
+    }
+
+    #[test]
+    fn deck_compositions() {
+        assert_eq!(Deck::shoe(6).len(), 312);
+        assert_eq!(Deck::shoe(0).len(), 0);
+
+        let short = Deck::stripped(Six);
+        assert_eq!(short.len(), 36);
+        assert!(short.remaining().iter().any(|card| card.rank == Ace));
+        assert!(short.remaining().iter().all(|card| card.rank >= Six));
+
+        assert_eq!(Deck::stripped(Seven).len(), 32);
+        assert_eq!(Deck::stripped(Two).len(), 52);
+
+        let cards = parse_cards("As Ks Qs").unwrap();
+        let mut deck = Deck::from_cards(cards.clone());
+        assert_eq!(deck.draw(3).unwrap(), &cards[..]);
+    }
+
+    #[test]
+    fn multi_deck_hands() {
+        use HandRanking::*;
+
+        let five = Hand::from_shoe(&parse_cards("As Ah Ac Ad").unwrap().repeat(2)[..5]);
+        assert_eq!(five.ranking(), &FiveOfAKind);
+
+        let flush_five = Hand::from_shoe(&[parse_cards("Qh").unwrap()[0]; 5]);
+        assert_eq!(flush_five.ranking(), &FlushFive);
+        assert!(flush_five > five);
+
+        let [king, nine, five, two] = parse_cards("Ks 9s 5s 2s").unwrap()[..] else {
+            unreachable!()
+        };
+        assert_eq!(
+            Hand::from_shoe(&[king, king, nine, five, two]).ranking(),
+            &Flush
+        );
+        assert_eq!(
+            Hand::from_shoe(&[king, king, king, five, five]).ranking(),
+            &FullHouse
         );
 
-        assert!(hand < other_hand);
-        assert_eq!(hand.compare(&other_hand), std::cmp::Ordering::Less);
+        // the pair in a flush doesn't help it
+        let [seven, five, three, two] = parse_cards("7s 5s 3s 2s").unwrap()[..] else {
+            unreachable!()
+        };
+        let paired = Hand::from_shoe(&[seven, seven, five, three, two]);
+        let ace_high = Hand::from_shoe(&parse_cards("As Ks Qs Js 9s").unwrap());
+        assert_eq!(paired.compare(&ace_high), std::cmp::Ordering::Less);
+
+        let other = Hand::from_shoe(&parse_cards("7s 6s 5s 3s 2s").unwrap());
+        assert_eq!(paired.compare(&other), std::cmp::Ordering::Greater);
+    }
+
+    #[test]
+    fn joker_deck() {
+        let deck = Deck::with_jokers(2);
+
+        assert_eq!(deck.len(), 54);
+        assert_eq!(deck.remaining().iter().filter(|c| c.is_joker()).count(), 2);
+        assert_eq!(find_duplicate(deck.remaining()), None);
+        assert_eq!("Joker".parse::<Card>(), Ok(Card::JOKER));
+    }
+
+    #[test]
+    fn jokers_are_wild() {
+        use HandRanking::*;
+
+        let hand = "Jk Ks Qs Js Ts".parse::<Hand>().unwrap();
+        assert_eq!(hand.ranking(), &RoyalFlush);
+        assert!(!hand.is_natural());
+        assert_eq!(
+            hand.substitutions(),
+            &[(Card::JOKER, "As".parse().unwrap())]
+        );
+
+        let natural = "As Ks Qs Js Ts".parse::<Hand>().unwrap();
+        assert!(natural.is_natural());
+        assert_eq!(natural.compare(&hand), std::cmp::Ordering::Equal);
+
+        let hand = "Jk As Ah Ac Ad".parse::<Hand>().unwrap();
+        assert_eq!(hand.ranking(), &FiveOfAKind);
+
+        let hand = "Jk 2c 5d 9h Kc".parse::<Hand>().unwrap();
+        assert_eq!(hand.ranking(), &Pair);
+        assert_eq!(hand.runs()[0][0].rank, King);
+    }
+
+    #[test]
+    fn deuces_wild() {
+        use HandRanking::*;
+
+        let deuces = WildCards::ranks(&[Two]);
+
+        let cards = parse_cards("2s 2h 7d 7c Kh").unwrap();
+        assert_eq!(Hand::with_wilds(&cards, &deuces).ranking(), &FourOfAKind);
+        assert_eq!(Hand::new(&cards).ranking(), &TwoPair);
+
+        let cards = parse_cards("2s 2h 2d 2c 9h").unwrap();
+        assert_eq!(Hand::with_wilds(&cards, &deuces).ranking(), &FiveOfAKind);
+
+        let cards = parse_cards("2s 6h 7h 8h 9h").unwrap();
+        let hand = Hand::with_wilds(&cards, &deuces);
+        assert_eq!(hand.ranking(), &StraightFlush);
+        assert_eq!(hand.substitutions()[0].1, "Th".parse().unwrap());
+    }
+
+    #[test]
+    fn best_five_of_seven() {
+        use HandRanking::*;
+
+        let hole = parse_cards("As Ks").unwrap();
+        let board = parse_cards("Qs Js Ts 2d 3c").unwrap();
+        let hand = Hand::best_of_hole_and_board(&hole, &board);
+        assert_eq!(hand.ranking(), &RoyalFlush);
+        assert_eq!(hand.cards().len(), 5);
+
+        // the board plays, both players split
+        let board = parse_cards("9c 9d 9h 9s Ah").unwrap();
+        let hand = Hand::best_of_hole_and_board(&parse_cards("Kd Qd").unwrap(), &board);
+        let other_hand = Hand::best_of_hole_and_board(&parse_cards("2c 3c").unwrap(), &board);
+        assert_eq!(hand.ranking(), &FourOfAKind);
+        assert_eq!(hand.compare(&other_hand), std::cmp::Ordering::Equal);
+
+        // kicker decides
+        let board = parse_cards("Kh Kc 7d 4s 2h").unwrap();
+        let hand = Hand::best_of_hole_and_board(&parse_cards("Ad 3c").unwrap(), &board);
+        let other_hand = Hand::best_of_hole_and_board(&parse_cards("Qd Jc").unwrap(), &board);
+        assert_eq!(hand.ranking(), &Pair);
+        assert!(hand > other_hand);
+
+        let hand = Hand::best_of(&parse_cards("8h 8d 8c 5s 5d 2c").unwrap());
+        assert_eq!(hand.ranking(), &FullHouse);
+    }
+
+    #[test]
+    fn descriptions() {
+        let describe = |s: &str| s.parse::<Hand>().unwrap().describe();
+
+        assert_eq!(
+            describe("Kh 7c Ks 7d Ah"),
+            "Two Pair, Kings and Sevens, Ace kicker"
+        );
+        assert_eq!(describe("5c 4d 3h 2s Ah"), "Five-high straight, the wheel");
+        assert_eq!(describe("9c 8d 7h 6s 5h"), "Nine-high straight");
+        assert_eq!(
+            describe("Ah 9h Kh 4h 7h"),
+            "Ace-high flush, King-Nine-Seven-Four"
+        );
+        assert_eq!(
+            describe("Jh Jd 2c 9s Ah"),
+            "Pair of Jacks, Ace-Nine-Two kickers"
+        );
+        assert_eq!(
+            describe("Qh 9d 7c 4s 3h"),
+            "High Card, Queen, Nine-Seven-Four-Three kickers"
+        );
+        assert_eq!(describe("6h 6d 6c Ts Th"), "Full House, Sixes full of Tens");
+        assert_eq!(
+            describe("9h 9d 9c 9s Ah"),
+            "Four of a Kind, Nines, Ace kicker"
+        );
+        assert_eq!(
+            describe("As 2s 3s 4s 5s"),
+            "Five-high straight flush, the steel wheel"
+        );
+        assert_eq!(describe("Ts Js Qs Ks As"), "Royal Flush");
+        assert_eq!(describe("Ts Js Qs Ks Jk"), "Wild Royal Flush");
+        assert_eq!(describe("Jk As Ah Ac Ad"), "Five of a Kind, Aces");
+    }
+
+    #[test]
+    fn short_deck() {
+        use HandRanking::*;
+
+        let rules = RankingRules::short_deck();
+        let hand = |s: &str| Hand::with_rules(&parse_cards(s).unwrap(), &rules);
+
+        assert_eq!(rules.precedence(Flush), Some(6));
+        assert_eq!(rules.precedence(FiveOfAKind), None);
+
+        let flush = hand("Ah 9h Kh 6h 7h");
+        let full_house = hand("6h 6d 6c Ts Th");
+        assert_eq!(flush.ranking(), &Flush);
+        assert!(flush > full_house);
+
+        // the ace plays low under the six, and that's the lowest straight
+        let low_straight = hand("Ac 6d 7h 8s 9h");
+        assert_eq!(low_straight.ranking(), &Straight);
+        assert_eq!(low_straight.describe(), "Nine-high straight");
+        assert!(hand("6c 7d 8h 9s Th") > low_straight);
+        assert_eq!(hand("6s 7s 8s 9s As").ranking(), &StraightFlush);
+    }
+
+    #[test]
+    fn three_card() {
+        use HandRanking::*;
+
+        let rules = RankingRules::three_card();
+        let hand = |s: &str| Hand::with_rules(&parse_cards(s).unwrap(), &rules);
+
+        let straight = hand("9c 8d 7h");
+        let flush = hand("Ah Kh 9h");
+        assert_eq!(straight.ranking(), &Straight);
+        assert!(straight > flush);
+        assert!(hand("2c 2d 2h") > hand("Qs Kd Ah"));
+        assert!(hand("Qs Ks As") > hand("2c 2d 2h"));
+        assert_eq!(hand("Qs Ks As").ranking(), &RoyalFlush);
+        assert_eq!(hand("3c 2d Ah").ranking(), &Straight);
+        assert!(hand("4c 3d 2h") > hand("3c 2d Ah"));
+        assert_eq!(hand("Kc Kd 2h").describe(), "Pair of Kings, Two kicker");
+        assert_eq!(hand("5c 5d 5h").describe(), "Three of a Kind, Fives");
+
+        let best = Hand::best_of_with_rules(&parse_cards("Ah Kh 9h 8d 7c").unwrap(), &rules);
+        assert_eq!(best.ranking(), &Straight);
+    }
+
+    #[test]
+    fn wraparound() {
+        use HandRanking::*;
+
+        let cards = parse_cards("Qc Kd Ah 2s 3h").unwrap();
+        assert_eq!(Hand::new(&cards).ranking(), &HighCard);
+
+        let rules = RankingRules {
+            wraparound: true,
+            ..RankingRules::standard()
+        };
+        let around = Hand::with_rules(&cards, &rules);
+        assert_eq!(around.ranking(), &Straight);
+        assert_eq!(around.describe(), "Three-high straight");
+        assert!(Hand::with_rules(&parse_cards("Kc Ad 2h 3s 4h").unwrap(), &rules) > around);
+        assert_eq!(
+            Hand::with_rules(&parse_cards("Ts Js Qs Ks As").unwrap(), &rules).ranking(),
+            &RoyalFlush
+        );
     }
 }
@@ -0,0 +1,355 @@
+use crate::card::Card;
+
+/// A set of distinct cards from a single deck, stored as one bit per card index
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+
+    // every card in a standard 52-card deck
+    pub const FULL: CardSet = CardSet((1 << 52) - 1);
+
+    pub fn new() -> CardSet {
+        CardSet::EMPTY
+    }
+
+    // `None` if a bit is set that isn't a card's index. jokers come after the 52 cards, one per
+    // suit
+    pub fn from_bits(bits: u64) -> Option<CardSet> {
+        (bits >> 56 == 0).then_some(CardSet(bits))
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    fn bit(card: Card) -> u64 {
+        1 << card.to_index()
+    }
+
+    // returns whether the card was not already in the set
+    pub fn insert(&mut self, card: Card) -> bool {
+        let inserted = !self.contains(card);
+        self.0 |= Self::bit(card);
+        inserted
+    }
+
+    // returns whether the card was in the set
+    pub fn remove(&mut self, card: Card) -> bool {
+        let removed = self.contains(card);
+        self.0 &= !Self::bit(card);
+        removed
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & Self::bit(card) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn is_subset(&self, other: CardSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn is_disjoint(&self, other: CardSet) -> bool {
+        self.0 & other.0 == 0
+    }
+
+    // iterates in card index order
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+
+    // every subset of exactly `k` cards. take dead cards out first to leave them out, e.g.
+    // `(CardSet::FULL - dead).subsets(5)`
+    pub fn subsets(&self, k: usize) -> Subsets {
+        assert!(self.len() < 64, "CardSet::subsets: too many cards");
+
+        let mut members = [0; 64];
+        let mut bits = self.0;
+        for member in members.iter_mut().take(self.len()) {
+            *member = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+        }
+
+        Subsets {
+            members,
+            next: (k <= self.len()).then(|| (1 << k) - 1),
+            end: 1 << self.len(),
+        }
+    }
+}
+
+impl std::ops::BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl std::ops::BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl std::ops::Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+impl std::ops::BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: CardSet) {
+        self.0 |= other.0;
+    }
+}
+
+impl std::ops::BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: CardSet) {
+        self.0 &= other.0;
+    }
+}
+
+impl std::ops::SubAssign for CardSet {
+    fn sub_assign(&mut self, other: CardSet) {
+        self.0 &= !other.0;
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> CardSet {
+        let mut set = CardSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.insert(card);
+        }
+    }
+}
+
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> CardSet {
+        cards.iter().copied().collect()
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl std::fmt::Debug for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl std::fmt::Display for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let index = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+
+        Card::from_index(index as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+// each subset is numbered by a k-bit number below 2^n, where n is the size of the whole set,
+// and the nth bit of that number picks the nth card. the numbers are stepped through in order
+// with Gosper's hack, so nothing is allocated along the way
+pub struct Subsets {
+    // the card index of each card in the whole set, lowest first
+    members: [u8; 64],
+    next: Option<u64>,
+    end: u64,
+}
+
+impl Iterator for Subsets {
+    type Item = CardSet;
+
+    fn next(&mut self) -> Option<CardSet> {
+        let combination = self.next?;
+
+        self.next = match combination {
+            // there's only one way to choose no cards
+            0 => None,
+            _ => {
+                let lowest = combination & combination.wrapping_neg();
+                let ripple = combination + lowest;
+                let next = (((ripple ^ combination) >> 2) / lowest) | ripple;
+                (next < self.end).then_some(next)
+            }
+        };
+
+        let mut bits = 0;
+        let mut rest = combination;
+        while rest != 0 {
+            bits |= 1 << self.members[rest.trailing_zeros() as usize];
+            rest &= rest - 1;
+        }
+
+        Some(CardSet(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_cards, Deck};
+    use std::collections::HashSet;
+
+    #[test]
+    fn full_set_matches_deck() {
+        let deck = Deck::new();
+        let set = (0..deck.len()).map(|i| deck[i]).collect::<CardSet>();
+
+        assert_eq!(set, CardSet::FULL);
+        assert_eq!(set.len(), 52);
+        assert_eq!(set.iter().count(), 52);
+    }
+
+    #[test]
+    fn from_bits() {
+        assert_eq!(
+            CardSet::from_bits(CardSet::FULL.bits()),
+            Some(CardSet::FULL)
+        );
+
+        let jokers = CardSet::from_bits(0xf << 52).unwrap();
+        assert_eq!(jokers.len(), 4);
+        assert!(jokers.iter().all(|card| card.is_joker()));
+
+        assert_eq!(CardSet::from_bits(1 << 56), None);
+        assert_eq!(CardSet::from_bits(u64::MAX), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = CardSet::from(&parse_cards("As Kh Qd").unwrap()[..]);
+        let b = CardSet::from(&parse_cards("Kh Qd 2c").unwrap()[..]);
+
+        assert_eq!(
+            a | b,
+            CardSet::from(&parse_cards("As Kh Qd 2c").unwrap()[..])
+        );
+        assert_eq!(a & b, CardSet::from(&parse_cards("Kh Qd").unwrap()[..]));
+        assert_eq!(a - b, CardSet::from(&parse_cards("As").unwrap()[..]));
+        assert!((a & b).is_subset(a));
+        assert!((a - b).is_disjoint(b));
+    }
+
+    #[test]
+    fn insert_remove_iterate() {
+        let cards = parse_cards("Ad 2s Kc").unwrap();
+        let mut set = CardSet::new();
+
+        assert!(set.is_empty());
+        for &card in &cards {
+            assert!(set.insert(card));
+        }
+        assert!(!set.insert(cards[0]));
+        assert_eq!(set.len(), 3);
+
+        // index order: spades, then clubs, then diamonds
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![cards[1], cards[2], cards[0]]
+        );
+
+        assert!(set.remove(cards[2]));
+        assert!(!set.remove(cards[2]));
+        assert!(!set.contains(cards[2]));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn subsets() {
+        let set = CardSet::from(&parse_cards("As Kh Qd 2c").unwrap()[..]);
+        let pairs = set.subsets(2).collect::<Vec<_>>();
+
+        assert_eq!(pairs.len(), 6);
+        assert!(pairs
+            .iter()
+            .all(|pair| pair.len() == 2 && pair.is_subset(set)));
+        assert_eq!(pairs.iter().copied().collect::<HashSet<_>>().len(), 6);
+        assert_eq!(set.subsets(0).collect::<Vec<_>>(), vec![CardSet::EMPTY]);
+        assert_eq!(set.subsets(4).collect::<Vec<_>>(), vec![set]);
+        assert_eq!(set.subsets(5).count(), 0);
+
+        assert_eq!(CardSet::FULL.subsets(5).count(), 2_598_960);
+
+        let dead = CardSet::from(&parse_cards("Ah Ad").unwrap()[..]);
+        assert_eq!((CardSet::FULL - dead).subsets(5).count(), 2_118_760);
+        assert!((CardSet::FULL - dead)
+            .subsets(5)
+            .all(|hand| hand.is_disjoint(dead)));
+    }
+
+    #[test]
+    fn deck_combinations() {
+        let mut deck = Deck::new();
+
+        assert_eq!(deck.combinations::<5>().count(), 2_598_960);
+
+        let dead = parse_cards("Ah Ad").unwrap();
+        assert_eq!(deck.combinations_excluding::<5>(&dead).count(), 2_118_760);
+
+        deck.draw(50);
+        assert_eq!(deck.combinations::<2>().count(), 1);
+        assert_eq!(deck.combinations::<3>().count(), 0);
+    }
+}
@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+// every way to choose `K` items out of a slice, without caring about order
+// the choices come out in lexicographic order of their positions in the slice
+// see also `Deck::combinations`, and `CardSet::subsets` for sets of cards
+pub struct Combinations<'a, T: Clone, const K: usize> {
+    items: Cow<'a, [T]>,
+    indices: [usize; K],
+    done: bool,
+}
+
+pub fn combinations<T: Copy, const K: usize>(items: &[T]) -> Combinations<'_, T, K> {
+    Combinations::from_cow(Cow::Borrowed(items))
+}
+
+// like `combinations`, for items that aren't kept anywhere else
+pub fn into_combinations<T: Copy, const K: usize>(items: Vec<T>) -> Combinations<'static, T, K> {
+    Combinations::from_cow(Cow::Owned(items))
+}
+
+impl<'a, T: Copy, const K: usize> Combinations<'a, T, K> {
+    fn from_cow(items: Cow<'a, [T]>) -> Combinations<'a, T, K> {
+        let mut indices = [0; K];
+        for (i, index) in indices.iter_mut().enumerate() {
+            *index = i;
+        }
+
+        Combinations {
+            done: K > items.len(),
+            items,
+            indices,
+        }
+    }
+}
+
+impl<'a, T: Copy, const K: usize> Iterator for Combinations<'a, T, K> {
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        if self.done {
+            return None;
+        }
+
+        let combination = self.indices.map(|i| self.items[i]);
+
+        self.done = !advance(&mut self.indices, self.items.len());
+
+        Some(combination)
+    }
+}
+
+// like `Combinations`, for when how many to choose isn't known until runtime
+pub struct Choose<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+pub fn choose<T: Copy>(items: &[T], k: usize) -> Choose<'_, T> {
+    Choose {
+        items,
+        indices: (0..k).collect(),
+        done: k > items.len(),
+    }
+}
+
+impl<'a, T: Copy> Iterator for Choose<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let combination = self.indices.iter().map(|&i| self.items[i]).collect();
+
+        self.done = !advance(&mut self.indices, self.items.len());
+
+        Some(combination)
+    }
+}
+
+// find the rightmost index that can still move right, move it, and pack every index
+// after it in right behind it. false once there are no more combinations
+fn advance(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+        None => false,
+        Some(i) => {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choose() {
+        let items = [1, 2, 3, 4];
+
+        assert_eq!(
+            combinations::<_, 2>(&items).collect::<Vec<_>>(),
+            vec![[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]
+        );
+        assert_eq!(combinations::<_, 4>(&items).count(), 1);
+        assert_eq!(combinations::<_, 5>(&items).count(), 0);
+        assert_eq!(combinations::<_, 0>(&items).count(), 1);
+        assert_eq!(combinations::<_, 5>(&[0; 7]).count(), 21);
+
+        assert_eq!(
+            super::choose(&items, 3).collect::<Vec<_>>(),
+            combinations::<_, 3>(&items)
+                .map(|c| c.to_vec())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(super::choose(&items, 0).count(), 1);
+        assert_eq!(super::choose(&items, 5).count(), 0);
+    }
+}
@@ -0,0 +1,373 @@
+// hold'em all-in equity: how often each player's hole cards win, tie or lose once the rest of
+// the board is dealt
+//
+// hands are scored with `eval::strength`, which agrees with `Hand::compare` but is much faster,
+// since even the flop can leave a thousand boards to try for every player
+
+use crate::card::{find_duplicate, Card, Deck};
+use crate::combinations::{choose, combinations};
+use crate::eval::{strength, Strength};
+
+// `Method::Auto` enumerates every board when boards times players, the number of seven-card
+// hands that would be scored, is at most this
+pub const EXACT_LIMIT: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    // deal every possible rest of the board once
+    Exact,
+    // deal `trials` random boards from a prng seeded with `seed`, so results can be repeated
+    MonteCarlo { trials: u64, seed: [u64; 4] },
+    // exact when that's cheap enough, see `EXACT_LIMIT`, otherwise Monte Carlo
+    Auto { trials: u64, seed: [u64; 4] },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    TooFewPlayers(usize),
+    TooManyBoardCards(usize),
+    DuplicateCard(Card),
+    Joker,
+    // more cards are needed than are left in the deck
+    NotEnoughCards,
+    // a player's range has nothing left once blocked hands are taken out
+    EmptyRange(usize),
+}
+
+impl std::fmt::Display for EquityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use EquityError::*;
+
+        match self {
+            TooFewPlayers(count) => write!(f, "expected at least 2 players, found {}", count),
+            TooManyBoardCards(count) => {
+                write!(f, "expected at most 5 board cards, found {}", count)
+            }
+            DuplicateCard(card) => write!(f, "`{}` appears more than once", card.code()),
+            Joker => write!(f, "jokers are not supported"),
+            NotEnoughCards => write!(f, "not enough cards left to finish the board"),
+            EmptyRange(player) => write!(f, "player {} has no possible hands", player + 1),
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+// what boards are counted in: `u64` when every board counts once, or `f64` when boards are
+// weighted, like the hands dealt from a `Range`
+pub trait Count:
+    Copy + Default + PartialEq + std::ops::Add<Output = Self> + std::ops::AddAssign
+{
+    fn to_f64(self) -> f64;
+}
+
+impl Count for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Count for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+// one player's results over every board that was dealt
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Equity<T = u64> {
+    wins: T,
+    ties: T,
+    losses: T,
+    // how many pots this adds up to, with tied pots split evenly
+    share: f64,
+}
+
+impl<T: Count> Equity<T> {
+    pub fn wins(&self) -> T {
+        self.wins
+    }
+
+    pub fn ties(&self) -> T {
+        self.ties
+    }
+
+    pub fn losses(&self) -> T {
+        self.losses
+    }
+
+    pub fn boards(&self) -> T {
+        self.wins + self.ties + self.losses
+    }
+
+    pub fn win_percent(&self) -> f64 {
+        self.wins.to_f64() / self.boards().to_f64() * 100.0
+    }
+
+    pub fn tie_percent(&self) -> f64 {
+        self.ties.to_f64() / self.boards().to_f64() * 100.0
+    }
+
+    pub fn lose_percent(&self) -> f64 {
+        self.losses.to_f64() / self.boards().to_f64() * 100.0
+    }
+
+    // the share of the pot this player can expect, counting their part of split pots
+    pub fn equity_percent(&self) -> f64 {
+        self.share / self.boards().to_f64() * 100.0
+    }
+
+    fn add(&mut self, outcome: Outcome, weight: T) {
+        match outcome {
+            Outcome::Win => self.wins += weight,
+            Outcome::Tie(_) => self.ties += weight,
+            Outcome::Loss => self.losses += weight,
+        }
+        self.share += outcome.share() * weight.to_f64();
+    }
+}
+
+/// e.g. `win 81.71%  tie 0.44%  lose 17.85%  equity 81.93%`
+impl<T: Count> std::fmt::Display for Equity<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "win {:.2}%  tie {:.2}%  lose {:.2}%  equity {:.2}%",
+            self.win_percent(),
+            self.tie_percent(),
+            self.lose_percent(),
+            self.equity_percent()
+        )
+    }
+}
+
+// how one player did on one board
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Outcome {
+    Win,
+    // with how many players split the pot
+    Tie(usize),
+    Loss,
+}
+
+impl Outcome {
+    // how much of the pot the player gets
+    pub(crate) fn share(&self) -> f64 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Tie(winners) => 1.0 / *winners as f64,
+            Outcome::Loss => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<T = u64> {
+    // in the same order as the hole cards that were passed in
+    pub players: Vec<Equity<T>>,
+    // false for Monte Carlo results, which are only estimates
+    pub exact: bool,
+}
+
+// `board` can have anywhere from none to all five cards. dead cards are left out of the deck,
+// e.g. cards someone folded face up
+pub fn equity(
+    holes: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    method: Method,
+) -> Result<Report, EquityError> {
+    if holes.len() < 2 {
+        return Err(EquityError::TooFewPlayers(holes.len()));
+    }
+
+    let live = live_cards(&[holes.concat().as_slice(), board, dead].concat(), board)?;
+    let missing = 5 - board.len();
+
+    let exact = match method {
+        Method::Exact => true,
+        Method::MonteCarlo { .. } => false,
+        Method::Auto { .. } => {
+            let evaluations =
+                (choose_count(live.len(), missing) as u64).saturating_mul(holes.len() as u64);
+            evaluations <= EXACT_LIMIT
+        }
+    };
+
+    let mut players = vec![Equity::default(); holes.len()];
+
+    if exact {
+        for rest in choose(&live, missing) {
+            add(&mut players, showdown(holes, board, &rest), 1);
+        }
+    } else {
+        let (trials, seed) = match method {
+            Method::MonteCarlo { trials, seed } | Method::Auto { trials, seed } => (trials, seed),
+            Method::Exact => unreachable!(),
+        };
+
+        let mut prng = prng::Prng64::new(seed);
+        let mut deck = Deck::from_cards(live);
+
+        for _ in 0..trials {
+            deck.shuffle(&mut prng);
+            add(
+                &mut players,
+                showdown(holes, board, deck.draw(missing).unwrap()),
+                1,
+            );
+        }
+    }
+
+    Ok(Report { players, exact })
+}
+
+// checks the cards everyone can see, and returns the rest of the deck
+pub(crate) fn live_cards(known: &[Card], board: &[Card]) -> Result<Vec<Card>, EquityError> {
+    if board.len() > 5 {
+        return Err(EquityError::TooManyBoardCards(board.len()));
+    }
+
+    if let Some(card) = find_duplicate(known) {
+        return Err(EquityError::DuplicateCard(card));
+    }
+
+    if known.iter().any(Card::is_joker) {
+        return Err(EquityError::Joker);
+    }
+
+    let mut live = Deck::new();
+    live.remove(known);
+
+    if 5 - board.len() > live.remaining().len() {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    Ok(live.remaining().to_vec())
+}
+
+// count each player's outcome on one board, `weight` times
+pub(crate) fn add<T: Count>(players: &mut [Equity<T>], outcomes: Vec<Outcome>, weight: T) {
+    for (player, outcome) in players.iter_mut().zip(outcomes) {
+        player.add(outcome, weight);
+    }
+}
+
+// deal `rest` to finish the board and see how each player does
+pub(crate) fn showdown(holes: &[[Card; 2]], board: &[Card], rest: &[Card]) -> Vec<Outcome> {
+    let strengths = holes
+        .iter()
+        .map(|hole| best_of_seven(&[&hole[..], board, rest].concat()))
+        .collect::<Vec<_>>();
+
+    // the best hand wins, and everyone tied for best splits the pot
+    let best = *strengths.iter().max().unwrap();
+    let winners = strengths.iter().filter(|&&s| s == best).count();
+
+    strengths
+        .iter()
+        .map(|&strength| {
+            if strength != best {
+                Outcome::Loss
+            } else if winners == 1 {
+                Outcome::Win
+            } else {
+                Outcome::Tie(winners)
+            }
+        })
+        .collect()
+}
+
+fn best_of_seven(cards: &[Card]) -> Strength {
+    combinations::<_, 5>(cards)
+        .map(|five| strength(&five))
+        .max()
+        .unwrap()
+}
+
+// n choose k, saturating rather than overflowing
+pub(crate) fn choose_count(n: usize, k: usize) -> usize {
+    (0..k).fold(1usize, |count, i| count.saturating_mul(n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn hole(s: &str) -> [Card; 2] {
+        parse_cards(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn exact_on_the_turn() {
+        let holes = [hole("Ah Ad"), hole("Kc Ks")];
+        let board = parse_cards("Kd 7h 2c 3s").unwrap();
+
+        let report = equity(&holes, &board, &[], Method::Exact).unwrap();
+        assert!(report.exact);
+
+        // only the two aces left in 44 cards save the aces
+        let (aces, kings) = (report.players[0], report.players[1]);
+        assert_eq!((aces.wins(), aces.ties(), aces.losses()), (2, 0, 42));
+        assert_eq!((kings.wins(), kings.losses()), (42, 2));
+
+        // with one of those aces dead, there's only one left
+        let dead = parse_cards("Ac").unwrap();
+        let report = equity(&holes, &board, &dead, Method::Exact).unwrap();
+        assert_eq!(report.players[0].wins(), 1);
+        assert_eq!(report.players[0].boards(), 43);
+    }
+
+    #[test]
+    fn split_pots() {
+        let holes = [hole("2h 3d"), hole("4c 5c"), hole("Ah Ad")];
+        let board = parse_cards("Ts Js Qs Ks As").unwrap();
+
+        let report = equity(&holes, &board, &[], Method::Exact).unwrap();
+        for player in &report.players {
+            assert_eq!(player.ties(), 1);
+            assert!((player.equity_percent() - 100.0 / 3.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let holes = [hole("Ah As"), hole("Kh Ks")];
+        let method = Method::Auto {
+            trials: 20_000,
+            seed: [1, 2, 3, 4],
+        };
+
+        // preflop, every board would be 3.4 million evaluations
+        let report = equity(&holes, &[], &[], method).unwrap();
+        assert!(!report.exact);
+        assert_eq!(report.players[0].boards(), 20_000);
+        assert!((report.players[0].equity_percent() - 82.0).abs() < 2.0);
+        assert_eq!(equity(&holes, &[], &[], method).unwrap(), report);
+
+        // on the flop it's cheap enough to be exact
+        let board = parse_cards("Qd 7c 2h").unwrap();
+        let exact = equity(&holes, &board, &[], method).unwrap();
+        assert!(exact.exact);
+        assert_eq!(exact.players[0].boards(), 990);
+    }
+
+    #[test]
+    fn errors() {
+        let holes = [hole("Ah As"), hole("Kh Ks")];
+
+        assert_eq!(
+            equity(&holes[..1], &[], &[], Method::Exact),
+            Err(EquityError::TooFewPlayers(1))
+        );
+        assert_eq!(
+            equity(&holes, &parse_cards("Ah").unwrap(), &[], Method::Exact),
+            Err(EquityError::DuplicateCard(holes[0][0]))
+        );
+        assert_eq!(
+            equity(&holes, &[Card::JOKER], &[], Method::Exact),
+            Err(EquityError::Joker)
+        );
+    }
+}
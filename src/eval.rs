@@ -0,0 +1,384 @@
+// a table-driven evaluator for standard five-card hands
+//
+// every hand is looked up by either the set of ranks in a flush or the multiset of ranks
+// otherwise, so evaluating doesn't sort any vectors or allocate. the tables are built once,
+// the first time they're needed, by ranking one example hand per class with `Hand::compare`,
+// so the two can never disagree
+
+use crate::card::{Card, Hand, HandRanking, Rank, Suit};
+
+use std::sync::OnceLock;
+
+// how many different hands there are, if hands that tie count as the same
+pub const CLASS_COUNT: u16 = 7462;
+
+// how good a five-card hand is. a higher strength beats a lower one and equal strengths tie,
+// exactly like `Hand::compare`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Strength(u16);
+
+impl Strength {
+    // from 0 for the worst hand up to `CLASS_COUNT - 1` for a royal flush
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+
+    pub fn ranking(&self) -> HandRanking {
+        tables().rankings[self.0 as usize]
+    }
+
+    // the equivalence class, counted the usual way from 1 for a royal flush
+    // down to `CLASS_COUNT` for seven-five-four-three-two
+    pub fn class(&self) -> u16 {
+        CLASS_COUNT - self.0
+    }
+
+    pub fn from_class(class: u16) -> Option<Strength> {
+        match class {
+            1..=CLASS_COUNT => Some(Strength(CLASS_COUNT - class)),
+            _ => None,
+        }
+    }
+
+    // one hand in this class, standing in for all the others
+    pub fn example(&self) -> [Card; 5] {
+        tables().examples[self.0 as usize]
+    }
+}
+
+/// the category, then the ranks from most to least important, e.g. `Two Pair: K K 7 7 A`
+impl std::fmt::Display for Strength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hand = Hand::new(&self.example());
+
+        write!(f, "{}:", hand.ranking())?;
+
+        let runs = hand.runs().iter().rev().flatten();
+        let kickers = hand.kickers().iter().rev();
+        for card in runs.chain(kickers) {
+            write!(f, " {}", card.rank.to_string().trim())?;
+        }
+
+        Ok(())
+    }
+}
+
+// the strength of five cards from a single standard deck.
+// jokers and repeated cards (from a shoe) are not supported, use `Hand` for those
+pub fn strength(cards: &[Card; 5]) -> Strength {
+    assert!(
+        cards.iter().all(|card| !card.is_joker()),
+        "eval::strength: jokers are not supported"
+    );
+
+    let ranks = cards.map(rank_index);
+
+    let tables = tables();
+
+    let suit = cards[0].suit;
+    if cards.iter().all(|card| card.suit == suit) {
+        let mask = ranks.iter().fold(0, |mask, rank| mask | 1 << rank);
+        return Strength(tables.flushes[mask]);
+    }
+
+    Strength(tables.others[multiset_index(ranks)])
+}
+
+// 0 for a two, up to 12 for an ace
+fn rank_index(card: Card) -> usize {
+    usize::from(card.rank) - 2
+}
+
+const RANK_COUNT: usize = 13;
+
+// binomial coefficients n choose k, for n up to the number of multisets below
+const BINOMIAL: [[usize; 6]; RANK_COUNT + 5] = {
+    let mut table = [[0; 6]; RANK_COUNT + 5];
+    let mut n = 0;
+    while n < table.len() {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k < 6 {
+            table[n][k] = if n == 0 {
+                0
+            } else {
+                table[n - 1][k - 1] + table[n - 1][k]
+            };
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+};
+
+const MULTISET_COUNT: usize = BINOMIAL[RANK_COUNT + 4][5];
+
+// a unique number for each multiset of five ranks, from 0 up to (but not including) the
+// number of multisets. adding i to the i-th smallest rank turns the multiset into a set,
+// which the combinatorial number system then numbers
+fn multiset_index(mut ranks: [usize; 5]) -> usize {
+    ranks.sort_unstable();
+
+    ranks
+        .iter()
+        .enumerate()
+        .map(|(i, rank)| BINOMIAL[rank + i][i + 1])
+        .sum()
+}
+
+struct Tables {
+    // indexed by a bitmask of the five ranks in a flush
+    flushes: Vec<u16>,
+    // indexed by `multiset_index`
+    others: Vec<u16>,
+    // the category of each strength
+    rankings: Vec<HandRanking>,
+    // an example hand for each strength
+    examples: Vec<[Card; 5]>,
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+
+    TABLES.get_or_init(Tables::build)
+}
+
+enum Slot {
+    Flush(usize),
+    Other(usize),
+}
+
+impl Tables {
+    fn build() -> Tables {
+        let ranks = enum_iterator::all::<Rank>()
+            .filter(|rank| *rank != Rank::Joker)
+            .collect::<Vec<_>>();
+        let suits = enum_iterator::all::<Suit>().collect::<Vec<_>>();
+
+        // one example hand for every flush and every other multiset of ranks
+        let mut examples = vec![];
+
+        for mask in 0..1usize << RANK_COUNT {
+            if mask.count_ones() != 5 {
+                continue;
+            }
+
+            let cards = (0..RANK_COUNT)
+                .filter(|rank| mask >> rank & 1 == 1)
+                .map(|rank| Card {
+                    rank: ranks[rank],
+                    suit: Suit::Spade,
+                })
+                .collect::<Vec<_>>();
+
+            examples.push((Slot::Flush(mask), Hand::new(&cards), cards));
+        }
+
+        let mut multiset = [0; 5];
+        'multisets: loop {
+            let too_many =
+                (0..RANK_COUNT).any(|rank| multiset.iter().filter(|&&r| r == rank).count() > 4);
+
+            if !too_many {
+                // give each copy of a rank a different suit, and make sure it isn't a flush
+                let mut cards = multiset
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &rank)| Card {
+                        rank: ranks[rank],
+                        suit: suits[multiset[..i].iter().filter(|&&r| r == rank).count()],
+                    })
+                    .collect::<Vec<_>>();
+
+                if cards.iter().all(|card| card.suit == Suit::Spade) {
+                    cards[0].suit = Suit::Heart;
+                }
+
+                examples.push((
+                    Slot::Other(multiset_index(multiset)),
+                    Hand::new(&cards),
+                    cards,
+                ));
+            }
+
+            // next multiset, as non-decreasing rank indices
+            let mut i = multiset.len();
+            loop {
+                if i == 0 {
+                    break 'multisets;
+                }
+                i -= 1;
+
+                if multiset[i] + 1 < RANK_COUNT {
+                    multiset[i] += 1;
+                    for j in i + 1..multiset.len() {
+                        multiset[j] = multiset[i];
+                    }
+                    break;
+                }
+            }
+        }
+
+        examples.sort_by(|(_, a, _), (_, b, _)| a.compare(b));
+
+        let mut tables = Tables {
+            flushes: vec![0; 1 << RANK_COUNT],
+            others: vec![0; MULTISET_COUNT],
+            rankings: vec![],
+            examples: vec![],
+        };
+
+        let mut previous: Option<&Hand> = None;
+        for (slot, hand, cards) in &examples {
+            let is_new_class = match previous {
+                None => true,
+                Some(previous) => previous.compare(hand) != std::cmp::Ordering::Equal,
+            };
+
+            if is_new_class {
+                tables.rankings.push(*hand.ranking());
+                tables.examples.push(cards[..].try_into().unwrap());
+            }
+
+            let strength = (tables.rankings.len() - 1) as u16;
+
+            match slot {
+                Slot::Flush(mask) => tables.flushes[*mask] = strength,
+                Slot::Other(index) => tables.others[*index] = strength,
+            }
+
+            previous = Some(hand);
+        }
+
+        tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_cards, Deck};
+
+    fn five(s: &str) -> [Card; 5] {
+        parse_cards(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn multiset_indices_are_dense() {
+        assert_eq!(MULTISET_COUNT, 6188);
+        assert_eq!(multiset_index([0; 5]), 0);
+        assert_eq!(multiset_index([12; 5]), MULTISET_COUNT - 1);
+    }
+
+    #[test]
+    fn orders_like_compare() {
+        let royal = strength(&five("As Ks Qs Js Ts"));
+        let wheel = strength(&five("Ah 2s 3s 4s 5s"));
+        let six_high = strength(&five("2s 3s 4s 5s 6h"));
+
+        assert_eq!(royal.ranking(), HandRanking::RoyalFlush);
+        assert_eq!(wheel.ranking(), HandRanking::Straight);
+        assert!(wheel < six_high);
+        assert!(six_high < royal);
+        assert_eq!(
+            strength(&five("Ks Kh 9c 5d 2s")),
+            strength(&five("Kc Kd 9s 5h 2c"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "jokers are not supported")]
+    fn rejects_jokers() {
+        strength(&five("Jk As Ks Qs Js"));
+    }
+
+    // walks every five-card hand, checking that it falls in a class whose example hand
+    // compares as equal. together with the classes being built in sorted order, this means
+    // comparing strengths is the same as `Hand::compare`
+    #[test]
+    fn exhaustive_cross_check() {
+        let tables = tables();
+        assert_eq!(tables.rankings.len(), CLASS_COUNT as usize);
+
+        let deck = Deck::new();
+        let cards = (0..deck.len()).map(|i| deck[i]).collect::<Vec<_>>();
+
+        let mut examples: Vec<Option<Hand>> = (0..tables.rankings.len()).map(|_| None).collect();
+        let mut count = 0;
+
+        for five in crate::combinations::combinations::<_, 5>(&cards) {
+            let strength = strength(&five);
+            let hand = Hand::new(&five);
+
+            assert_eq!(strength.ranking(), *hand.ranking());
+
+            match &examples[strength.value() as usize] {
+                None => examples[strength.value() as usize] = Some(hand),
+                Some(example) => {
+                    assert_eq!(
+                        example.compare(&hand),
+                        std::cmp::Ordering::Equal,
+                        "{}",
+                        hand
+                    )
+                }
+            }
+
+            count += 1;
+        }
+
+        assert_eq!(count, 2_598_960);
+
+        let examples = examples.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+        for pair in examples.windows(2) {
+            assert_eq!(pair[0].compare(&pair[1]), std::cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn equivalence_classes() {
+        use HandRanking::*;
+
+        assert_eq!(strength(&five("Ah Kh Qh Jh Th")).class(), 1);
+        assert_eq!(strength(&five("Kh Qh Jh Th 9h")).class(), 2);
+        assert_eq!(strength(&five("7c 5d 4h 3s 2c")).class(), CLASS_COUNT);
+
+        let mut counts = std::collections::BTreeMap::new();
+        for class in 1..=CLASS_COUNT {
+            let strength = Strength::from_class(class).unwrap();
+            assert_eq!(strength.class(), class);
+            assert_eq!(super::strength(&strength.example()), strength);
+            *counts.entry(strength.ranking()).or_insert(0) += 1;
+        }
+
+        assert_eq!(Strength::from_class(0), None);
+        assert_eq!(Strength::from_class(CLASS_COUNT + 1), None);
+
+        let expected = [
+            (HighCard, 1277),
+            (Pair, 2860),
+            (TwoPair, 858),
+            (ThreeOfAKind, 858),
+            (Straight, 10),
+            (Flush, 1277),
+            (FullHouse, 156),
+            (FourOfAKind, 156),
+            (StraightFlush, 9),
+            (RoyalFlush, 1),
+        ];
+        assert_eq!(counts.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn class_descriptions() {
+        let describe = |s| strength(&five(s)).to_string();
+
+        assert_eq!(describe("7h Kd 7s Ac Kc"), "Two Pair: K K 7 7 A");
+        assert_eq!(describe("Ah 2s 3s 4s 5s"), "Straight: 5 4 3 2 A");
+        assert_eq!(describe("Td Jd Qd Kd Ad"), "Royal Flush: A K Q J 10");
+        assert_eq!(
+            Strength::from_class(CLASS_COUNT).unwrap().to_string(),
+            "High Card: 7 5 4 3 2"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// split pots, where the best high hand wins half and the best qualifying low hand wins the
+// other half, as in omaha hi/lo and stud eight or better
+
+use crate::card::{Card, Hand, Rank};
+use crate::lowball::{LowHand, Lowball};
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitPot {
+    // every player who wins or ties for the high half, by their position in the input
+    pub high: Vec<usize>,
+    // every player who wins or ties for the low half, empty if no low qualified
+    pub low: Vec<usize>,
+}
+
+impl SplitPot {
+    pub fn has_low(&self) -> bool {
+        !self.low.is_empty()
+    }
+
+    // the player who takes the whole pot alone, by winning both halves
+    // or by winning the high half when there is no low
+    pub fn scooper(&self) -> Option<usize> {
+        match (&self.high[..], &self.low[..]) {
+            ([high], []) => Some(*high),
+            ([high], [low]) if high == low => Some(*high),
+            _ => None,
+        }
+    }
+}
+
+// the best ace-to-five low out of five or more cards, if it's eight or better
+pub fn eight_or_better(cards: &[Card]) -> Option<LowHand> {
+    let hand = LowHand::best_of(cards, Lowball::AceToFive);
+
+    hand.qualifies(Rank::Eight).then_some(hand)
+}
+
+// split the pot between players who each make their best five out of their own cards,
+// e.g. the seven cards each player has in stud
+pub fn split_pot(players: &[&[Card]]) -> SplitPot {
+    let highs = players
+        .iter()
+        .map(|cards| Hand::best_of(cards))
+        .collect::<Vec<_>>();
+    let lows = players
+        .iter()
+        .map(|cards| eight_or_better(cards))
+        .collect::<Vec<_>>();
+
+    split_pot_between(&highs, &lows)
+}
+
+// split the pot once each player's best high hand and qualifying low hand are known
+pub fn split_pot_between(highs: &[Hand], lows: &[Option<LowHand>]) -> SplitPot {
+    assert_eq!(highs.len(), lows.len());
+
+    let high = winners(highs.iter().map(Some), Hand::compare);
+    let low = winners(lows.iter().map(Option::as_ref), LowHand::compare);
+
+    SplitPot { high, low }
+}
+
+// the positions of the best hands, skipping any that are missing
+fn winners<'a, T: 'a>(
+    hands: impl Iterator<Item = Option<&'a T>>,
+    compare: impl Fn(&T, &T) -> Ordering,
+) -> Vec<usize> {
+    let mut best: Option<&T> = None;
+    let mut winners = vec![];
+
+    for (i, hand) in hands.enumerate() {
+        let Some(hand) = hand else {
+            continue;
+        };
+
+        let order = match best {
+            None => Ordering::Greater,
+            Some(best) => compare(hand, best),
+        };
+
+        match order {
+            Ordering::Greater => {
+                best = Some(hand);
+                winners = vec![i];
+            }
+            Ordering::Equal => winners.push(i),
+            Ordering::Less => (),
+        }
+    }
+
+    winners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn split(players: &[&str]) -> SplitPot {
+        let players = players
+            .iter()
+            .map(|s| parse_cards(s).unwrap())
+            .collect::<Vec<_>>();
+
+        split_pot(&players.iter().map(Vec::as_slice).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn high_and_low_split() {
+        let pot = split(&["Kh Kd Ks 9c 9h 2d Jc", "Ah 2c 3d 6s 8h Qd Jh"]);
+
+        assert_eq!(pot.high, vec![0]);
+        assert_eq!(pot.low, vec![1]);
+        assert_eq!(pot.scooper(), None);
+    }
+
+    #[test]
+    fn wheel_scoops() {
+        let pot = split(&["Ah 2c 3d 4s 5h Kd Kh", "Qh Qc Jd Js 8h 8d 2h"]);
+
+        assert_eq!(pot.high, vec![0]);
+        assert_eq!(pot.low, vec![0]);
+        assert_eq!(pot.scooper(), Some(0));
+    }
+
+    #[test]
+    fn no_low_qualifies() {
+        let pot = split(&["Ah Ac 9d 9s Th Kd Qh", "Kc Qc Jd Js 9h 7d 2h"]);
+
+        assert!(!pot.has_low());
+        assert_eq!(pot.high, vec![0]);
+        assert_eq!(pot.scooper(), Some(0));
+    }
+
+    #[test]
+    fn tied_lows() {
+        let pot = split(&[
+            "Ah 2c 4d 6s 7h Kd Kh",
+            "As 2d 4c 6h 7c Qs Qh",
+            "9h 9c 9d Ts Th 8d 3h",
+        ]);
+
+        assert_eq!(pot.high, vec![2]);
+        assert_eq!(pot.low, vec![0, 1]);
+        assert_eq!(pot.scooper(), None);
+    }
+}
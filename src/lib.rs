@@ -1,30 +1,74 @@
-mod card;
-mod score;
+pub mod card;
+pub mod card_set;
+pub mod combinations;
+pub mod equity;
+pub mod eval;
+pub mod hilo;
+pub mod lowball;
+pub mod omaha;
+pub mod outs;
+pub mod range;
+pub mod score;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod stats;
 mod ui;
 
-use card::{Card, Deck, Hand};
+use card::{Card, Deck};
+use score::{Paytable, MAX_COINS};
 use ui::HandRankingDisplay;
 
-pub fn run_console_game() -> Result<(), Box<dyn std::error::Error>> {
-    let mut state = GameState::new();
+pub fn run_console_game(paytable: Paytable) -> Result<(), Box<dyn std::error::Error>> {
+    let mut state = GameState::with_paytable(paytable);
 
     state.main_loop()
 }
 
+// print how often each kind of hand comes up, either counted over every possible hand
+// or estimated from `hands` random deals
+pub fn run_stats(exhaustive: bool, hands: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let frequencies = if exhaustive {
+        println!("dealing all 2598960 hands...");
+        stats::Frequencies::exhaustive()
+    } else {
+        println!("dealing {} random hands...", hands);
+        stats::Frequencies::sample(&mut prng::Prng64::new(get_prng_seed()), hands)
+    };
+
+    println!("{}", frequencies);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameState {
+    // a saved game gets a fresh seed when it's loaded
+    #[cfg_attr(feature = "serde", serde(skip, default = "new_prng"))]
     prng: prng::Prng64,
     deck: Deck,
+    paytable: Paytable,
     score: usize,
     high_score: usize,
+    // the last bet, which is bet again unless the player changes it
+    coins: usize,
+    // how many points each coin is worth
+    denomination: usize,
 }
 
 impl GameState {
     pub fn new() -> GameState {
+        GameState::with_paytable(Paytable::classic())
+    }
+
+    pub fn with_paytable(paytable: Paytable) -> GameState {
         GameState {
             prng: prng::Prng64::new(get_prng_seed()),
-            deck: Deck::new(),
+            deck: paytable.deck(),
+            paytable,
             score: 100,
             high_score: 100,
+            coins: MAX_COINS,
+            denomination: 1,
         }
     }
 
@@ -33,27 +77,107 @@ impl GameState {
 
         let blank_ranking_display = HandRankingDisplay(None);
 
+        println!("playing {}", self.paytable.name());
+
         'main: loop {
+            if self.score == 0 {
+                println!("Sorry, You Lose!");
+                println!("High score:      {:>6} points", self.high_score);
+                break 'main;
+            }
+
+            // the bet comes out of the score before the cards are dealt
+            loop {
+                input.clear();
+
+                println!("Total score:     {:>6} points", self.score);
+                println!(
+                    "Betting {} coin(s) worth {} point(s) each. Press Enter to deal, or",
+                    self.coins, self.denomination
+                );
+                println!(
+                    "enter 1 to {} coins, and a coin value after an 'x' to change it, e.g. '3 x 10': ",
+                    MAX_COINS
+                );
+                std::io::stdin().read_line(&mut input)?;
+
+                if input.contains("quit") {
+                    break 'main;
+                }
+
+                let (coins, denomination) = match parse_bet(&input, self.coins, self.denomination) {
+                    Some(bet) => bet,
+                    None => {
+                        println!("That's not a bet.");
+                        continue;
+                    }
+                };
+
+                if coins * denomination > self.score {
+                    println!("You only have {} points.", self.score);
+                    continue;
+                }
+
+                self.coins = coins;
+                self.denomination = denomination;
+                self.score -= coins * denomination;
+                break;
+            }
+
             println!("shuffling...");
 
             self.deck.shuffle(&mut self.prng);
 
+            let initial_cards = self
+                .deck
+                .draw(5)
+                .expect("a fresh deck has at least five cards")
+                .to_vec();
+
             let mut held_cards = [false; 5];
 
             let hand = loop {
                 input.clear();
 
-                let initial_cards = self.deck.peek_five_cards(0);
-
-                display_cards_and_holds(initial_cards, &held_cards[..]);
+                display_cards_and_holds(&initial_cards, &held_cards[..]);
                 println!();
                 println!("{}", blank_ranking_display);
                 println!();
 
                 println!();
                 println!();
+                println!("Enter 'outs' to see what you're drawing to.");
                 println!("Enter a number from 1 to 5 to hold / unhold. Enter 'go' to finish: ");
                 std::io::stdin().read_line(&mut input)?;
+
+                if input.contains("outs") {
+                    let (held, discarded): (Vec<_>, Vec<_>) = initial_cards
+                        .iter()
+                        .zip(held_cards)
+                        .partition(|(_, held)| *held);
+                    let held = held.into_iter().map(|(&card, _)| card).collect::<Vec<_>>();
+                    let discarded = discarded
+                        .into_iter()
+                        .map(|(&card, _)| card)
+                        .collect::<Vec<_>>();
+
+                    println!();
+                    if discarded.is_empty() {
+                        println!("Every card is held, there's nothing to draw.");
+                    } else {
+                        let outs = outs::outs_with_rules(
+                            &held,
+                            &discarded,
+                            discarded.len(),
+                            self.paytable.deck(),
+                            &self.paytable.rules(),
+                        );
+                        println!("{}", outs);
+                    }
+                    println!();
+                    continue;
+                }
+
                 for i in 0..5 {
                     if input.contains(&(i + 1).to_string()) {
                         held_cards[i] = !held_cards[i];
@@ -65,15 +189,16 @@ impl GameState {
                 }
 
                 if input.contains("go") || input.contains("deal") || input.clone().trim() == "" {
-                    break do_hold(&self.deck, initial_cards, &held_cards[..]);
+                    break do_hold(&mut self.deck, &initial_cards, &held_cards[..]);
                 }
             };
 
-            let hand = Hand::new(&hand[..]);
+            let hand = self.paytable.hand(&hand[..]);
 
             input.clear();
 
-            let score_this_hand = score::score_hand(&hand);
+            let score_this_hand =
+                self.paytable.payout_for_bet(&hand, self.coins) * self.denomination;
 
             self.score += score_this_hand;
 
@@ -84,55 +209,54 @@ impl GameState {
             display_cards_and_holds(&hand.cards()[..], &[false; 5]);
             println!("");
 
-            let ranking_display = HandRankingDisplay(Some(hand.ranking().clone()));
+            let ranking_display = HandRankingDisplay(Some(self.paytable.pay_line(&hand)));
             println!("{}", ranking_display);
             println!();
 
+            println!("{}", hand.describe());
+
             println!("Score this hand: {:>6} points", score_this_hand);
+        }
 
-            if self.score < 5 {
-                println!("Sorry, You Lose!");
-                println!("High score:      {:>6} points", self.high_score);
-                break 'main;
-            }
+        Ok(())
+    }
+}
 
-            self.score = self.score - 5;
+// "3" or "3 x 10" is a number of coins and optionally what each is worth. a blank line
+// keeps the last bet
+fn parse_bet(input: &str, coins: usize, denomination: usize) -> Option<(usize, usize)> {
+    let input = input.trim();
 
-            println!("Total score:     {:>6} points", self.score);
-            println!("Press Enter to continue: ");
-            std::io::stdin().read_line(&mut input)?;
+    if input.is_empty() {
+        return Some((coins, denomination));
+    }
 
-            if input.contains("quit") {
-                break 'main;
-            }
-        }
+    let (coins, denomination) = match input.split_once('x') {
+        Some((coins, denomination)) => (coins.trim(), denomination.trim().parse().ok()?),
+        None => (input, denomination),
+    };
+    let coins = coins.parse().ok()?;
 
-        Ok(())
-    }
+    ((1..=MAX_COINS).contains(&coins) && denomination > 0).then_some((coins, denomination))
 }
 
-fn do_hold(deck: &Deck, initial_hand: &[Card], held: &[bool]) -> Vec<Card> {
+fn do_hold(deck: &mut Deck, initial_hand: &[Card], held: &[bool]) -> Vec<Card> {
     assert_eq!(
         initial_hand.len(),
         held.len(),
         "do_hold: arguments not the same length"
     );
 
-    let mut index = initial_hand.len();
-
-    assert!(
-        deck.len() >= index,
-        "do_hold: deck doesn't have enough cards left to deal"
-    );
-
     let mut new_hand = vec![];
 
-    for i in 0..index.clone() {
-        if held[i] {
-            new_hand.push(initial_hand[i].clone());
+    for (&card, &is_held) in initial_hand.iter().zip(held) {
+        if is_held {
+            new_hand.push(card);
         } else {
-            new_hand.push(deck.index(index).clone());
-            index += 1;
+            let replacement = deck
+                .draw(1)
+                .expect("do_hold: deck doesn't have enough cards left to deal");
+            new_hand.push(replacement[0]);
         }
     }
 
@@ -155,55 +279,9 @@ fn display_cards_and_holds(cards: &[Card], held: &[bool]) {
     println!();
 }
 
-fn _old_main() -> Result<(), Box<dyn std::error::Error>> {
-    // prng is short for Pseudo-Random Number Generator
-    let mut prng = prng::Prng64::new(get_prng_seed());
-
-    let mut hands_dealt = 0;
-    let mut hands_matched = 0;
-    let mut total_score = 0;
-
-    let mut deck = card::Deck::new();
-
-    for _ in 0..500 {
-        deck.shuffle(&mut prng);
-
-        for index in 0..5 {
-            let hand1 = deck._peek_poker_hand(index * 5, 5).unwrap();
-
-            let score = score::score_hand(&hand1);
-
-            //          let hand2 = deck.peek_poker_hand(index * 10, 5).unwrap();
-
-            hands_dealt += 1;
-
-            total_score += score as isize - 5;
-
-            //            if hand1.compare(&hand2) != hand1.old_compare(&hand2) {
-            if score >= 10 {
-                hands_matched += 1;
-
-                println!("{}", hand1._display_hand_with_info());
-                //                println!("{}", hand2.display_hand_with_info());
-                //                println!();
-            }
-        }
-    }
-
-    println!("hands dealt:     {:>10}", hands_dealt);
-    println!("matching hands:  {:>10}", hands_matched);
-
-    println!(
-        "percent:         {:>13.02}%",
-        (hands_matched as f64 / hands_dealt as f64) * 100.0
-    );
-
-    println!(
-        "average score:   {:>13.02}",
-        (total_score as f64 / hands_dealt as f64)
-    );
-
-    Ok(())
+#[cfg(feature = "serde")]
+fn new_prng() -> prng::Prng64 {
+    prng::Prng64::new(get_prng_seed())
 }
 
 fn get_prng_seed() -> [u64; 4] {
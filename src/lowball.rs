@@ -0,0 +1,236 @@
+// low hands, where the worst poker hand wins
+//
+// `LowHand::compare` keeps the meaning of `Hand::compare`: `Greater` means the better hand,
+// which in lowball is the lower one
+
+use crate::card::{find_duplicate, Card, HandRanking, Rank};
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lowball {
+    // aces are low and straights and flushes don't count, so five-four-three-two-ace is best.
+    // used for razz and the low half of split pots
+    AceToFive,
+    // aces are high and straights and flushes count against you, so seven-five-four-three-two
+    // is best. used for triple draw and single draw
+    DeuceToSeven,
+}
+
+#[derive(Debug)]
+pub struct LowHand {
+    hand: Vec<Card>,
+    rules: Lowball,
+    ranking: HandRanking,
+    // one number per distinct rank, most important first. lower is better
+    ranks: Vec<usize>,
+}
+
+impl LowHand {
+    pub fn new(hand: &[Card], rules: Lowball) -> LowHand {
+        assert_eq!(hand.len(), 5);
+
+        if let Some(card) = find_duplicate(hand) {
+            panic!("LowHand::new: {:?}appears more than once", card);
+        }
+
+        assert!(
+            hand.iter().all(|card| !card.is_joker()),
+            "LowHand::new: jokers are not supported"
+        );
+
+        let value = |rank: Rank| match (rules, rank) {
+            (Lowball::AceToFive, Rank::Ace) => 1,
+            _ => usize::from(rank),
+        };
+
+        // (how many, value) for each rank, biggest groups and then highest ranks first
+        let mut groups: Vec<(usize, usize)> = vec![];
+        for card in hand {
+            let value = value(card.rank);
+            match groups.iter_mut().find(|(_, v)| *v == value) {
+                Some((count, _)) => *count += 1,
+                None => groups.push((1, value)),
+            }
+        }
+        groups.sort_by(|a, b| b.cmp(a));
+
+        let counts = groups.iter().map(|(count, _)| *count).collect::<Vec<_>>();
+        let ranks = groups.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+
+        use HandRanking::*;
+
+        let ranking = match counts[..] {
+            [4, 1] => FourOfAKind,
+            [3, 2] => FullHouse,
+            [3, 1, 1] => ThreeOfAKind,
+            [2, 2, 1] => TwoPair,
+            [2, 1, 1, 1] => Pair,
+            _ => match rules {
+                Lowball::AceToFive => HighCard,
+                Lowball::DeuceToSeven => {
+                    let is_flush = hand.iter().all(|card| card.suit == hand[0].suit);
+                    let is_straight = ranks[0] - ranks[4] == 4;
+
+                    match (is_straight, is_flush) {
+                        (true, true) if ranks[0] == usize::from(Rank::Ace) => RoyalFlush,
+                        (true, true) => StraightFlush,
+                        (false, true) => Flush,
+                        (true, false) => Straight,
+                        (false, false) => HighCard,
+                    }
+                }
+            },
+        };
+
+        LowHand {
+            hand: hand.to_vec(),
+            rules,
+            ranking,
+            ranks,
+        }
+    }
+
+    // the best low hand out of five or more cards, e.g. all seven cards in razz
+    pub fn best_of(cards: &[Card], rules: Lowball) -> LowHand {
+        assert!(cards.len() >= 5, "LowHand::best_of: fewer than five cards");
+
+        let mut best: Option<LowHand> = None;
+
+        for five in crate::combinations::combinations::<_, 5>(cards) {
+            let hand = LowHand::new(&five, rules);
+
+            best = match best {
+                Some(best) if hand.compare(&best) != Ordering::Greater => Some(best),
+                _ => Some(hand),
+            };
+        }
+
+        best.unwrap()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.hand
+    }
+
+    pub fn rules(&self) -> Lowball {
+        self.rules
+    }
+
+    // the high-hand category this would be, if it counts against the hand under these rules
+    pub fn ranking(&self) -> &HandRanking {
+        &self.ranking
+    }
+
+    // whether this has no pairs, straights or flushes (if they count), and no card above
+    // `highest`. e.g. `Rank::Eight` for an eight-or-better qualifier
+    pub fn qualifies(&self, highest: Rank) -> bool {
+        let highest = match (self.rules, highest) {
+            (Lowball::AceToFive, Rank::Ace) => 1,
+            _ => usize::from(highest),
+        };
+
+        self.ranking == HandRanking::HighCard && self.ranks[0] <= highest
+    }
+
+    // `Greater` if this is the better low hand
+    pub fn compare(&self, other: &LowHand) -> Ordering {
+        assert_eq!(self.rules, other.rules, "LowHand::compare: different rules");
+
+        (other.ranking, &other.ranks).cmp(&(self.ranking, &self.ranks))
+    }
+}
+
+impl Ord for LowHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+}
+
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LowHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LowHand {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn low(s: &str, rules: Lowball) -> LowHand {
+        LowHand::new(&parse_cards(s).unwrap(), rules)
+    }
+
+    #[test]
+    fn ace_to_five() {
+        use Lowball::AceToFive;
+
+        let wheel = low("As 2s 3s 4s 5s", AceToFive);
+        assert_eq!(wheel.ranking(), &HandRanking::HighCard);
+        assert!(wheel > low("6h 4d 3c 2s Ah", AceToFive));
+        // straights don't count against you, but pairs do
+        assert!(low("8h 7d 6c 5s 4h", AceToFive) > low("8c 7h 6d 5h 5d", AceToFive));
+        assert!(low("Kh Qd Jc 9s 8h", AceToFive) > low("Ah Ad 2c 3s 4h", AceToFive));
+        assert_eq!(
+            low("7h 5d 4c 3s 2h", AceToFive).compare(&low("7s 5h 4d 3c 2c", AceToFive)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "jokers are not supported")]
+    fn rejects_jokers() {
+        low("Jk 2s 3s 4s 5s", Lowball::AceToFive);
+    }
+
+    #[test]
+    fn deuce_to_seven() {
+        use Lowball::DeuceToSeven;
+
+        let number_one = low("7h 5d 4c 3s 2h", DeuceToSeven);
+        assert!(number_one > low("8h 6d 5c 4s 2h", DeuceToSeven));
+
+        // no wheel: this is just ace-high
+        let ace_high = low("As 2d 3c 4s 5h", DeuceToSeven);
+        assert_eq!(ace_high.ranking(), &HandRanking::HighCard);
+        assert!(low("Kh Qd Jc 9s 8h", DeuceToSeven) > ace_high);
+
+        let straight = low("7h 6d 5c 4s 3h", DeuceToSeven);
+        assert_eq!(straight.ranking(), &HandRanking::Straight);
+        assert!(low("Ah Kd Qc Js 9h", DeuceToSeven) > straight);
+
+        let flush = low("7h 5h 4h 3h 2h", DeuceToSeven);
+        assert_eq!(flush.ranking(), &HandRanking::Flush);
+        assert!(low("2h 2d 3c 4s 5h", DeuceToSeven) > flush);
+    }
+
+    #[test]
+    fn qualifiers() {
+        use Lowball::*;
+
+        assert!(low("8h 7d 6c 5s 4h", AceToFive).qualifies(Rank::Eight));
+        assert!(low("8h 7d 3c 2s Ah", AceToFive).qualifies(Rank::Eight));
+        assert!(!low("9h 7d 3c 2s Ah", AceToFive).qualifies(Rank::Eight));
+        assert!(!low("7h 7d 3c 2s Ah", AceToFive).qualifies(Rank::Eight));
+        assert!(!low("8h 7d 6c 5s 4h", DeuceToSeven).qualifies(Rank::Eight));
+    }
+
+    #[test]
+    fn razz() {
+        let hand = LowHand::best_of(
+            &parse_cards("Kh Kd 7c 4s 2h Ac 3d").unwrap(),
+            Lowball::AceToFive,
+        );
+
+        assert_eq!(hand, low("7c 4s 2h Ac 3d", Lowball::AceToFive));
+    }
+}
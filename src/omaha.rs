@@ -0,0 +1,124 @@
+// omaha, where a hand is made from exactly two hole cards and exactly three board cards.
+// works for four-card omaha and for five-card (or bigger) variants
+
+use crate::card::{find_duplicate, Card, Hand, Rank};
+use crate::combinations::combinations;
+use crate::hilo::{split_pot_between, SplitPot};
+use crate::lowball::{LowHand, Lowball};
+
+use std::cmp::Ordering;
+
+// every legal hand: two hole cards and three board cards
+fn legal_hands<'a>(hole: &'a [Card], board: &'a [Card]) -> impl Iterator<Item = [Card; 5]> + 'a {
+    assert!(hole.len() >= 2, "omaha: fewer than two hole cards");
+    assert!(board.len() >= 3, "omaha: fewer than three board cards");
+
+    if let Some(card) = find_duplicate(&[hole, board].concat()) {
+        panic!("omaha: {:?}appears more than once", card);
+    }
+
+    combinations::<_, 2>(hole).flat_map(move |two| {
+        combinations::<_, 3>(board).map(move |three| [two[0], two[1], three[0], three[1], three[2]])
+    })
+}
+
+pub fn best_hand(hole: &[Card], board: &[Card]) -> Hand {
+    let mut best: Option<Hand> = None;
+
+    for five in legal_hands(hole, board) {
+        let hand = Hand::new(&five);
+
+        best = match best {
+            Some(best) if hand.compare(&best) != Ordering::Greater => Some(best),
+            _ => Some(hand),
+        };
+    }
+
+    best.unwrap()
+}
+
+// the best eight-or-better ace-to-five low, for omaha hi/lo
+pub fn best_low(hole: &[Card], board: &[Card]) -> Option<LowHand> {
+    let mut best: Option<LowHand> = None;
+
+    for five in legal_hands(hole, board) {
+        let hand = LowHand::new(&five, Lowball::AceToFive);
+
+        if !hand.qualifies(Rank::Eight) {
+            continue;
+        }
+
+        best = match best {
+            Some(best) if hand.compare(&best) != Ordering::Greater => Some(best),
+            _ => Some(hand),
+        };
+    }
+
+    best
+}
+
+// omaha hi/lo, each player's hole cards against the same board
+pub fn split_pot(players: &[&[Card]], board: &[Card]) -> SplitPot {
+    let highs = players
+        .iter()
+        .map(|hole| best_hand(hole, board))
+        .collect::<Vec<_>>();
+    let lows = players
+        .iter()
+        .map(|hole| best_low(hole, board))
+        .collect::<Vec<_>>();
+
+    split_pot_between(&highs, &lows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{parse_cards, HandRanking};
+
+    fn best(hole: &str, board: &str) -> Hand {
+        best_hand(&parse_cards(hole).unwrap(), &parse_cards(board).unwrap())
+    }
+
+    #[test]
+    fn exactly_two_hole_cards() {
+        // one spade in the hand is not a flush, even with four on the board
+        let hand = best("As Kd Qc Jh", "2s 5s 8s 9s 2d");
+        assert_eq!(hand.ranking(), &HandRanking::Pair);
+
+        let hand = best("As Ks Qc Jh", "2s 5s 8s 9s 2d");
+        assert_eq!(hand.ranking(), &HandRanking::Flush);
+
+        // the board's quads can't all be used
+        let hand = best("2c 3d 7h 8s", "9c 9d 9h 9s Kh");
+        assert_eq!(hand.ranking(), &HandRanking::ThreeOfAKind);
+
+        // and four to a straight in the hand is still only two cards
+        let hand = best("6c 7d 8h 9s", "Ac Kd 2h 3s Js");
+        assert_eq!(hand.ranking(), &HandRanking::HighCard);
+    }
+
+    #[test]
+    fn five_card_omaha() {
+        let hand = best("Ah Kh 2c 7d 7s", "7c Qh Jh 4s 3d");
+        assert_eq!(hand.ranking(), &HandRanking::ThreeOfAKind);
+    }
+
+    #[test]
+    fn hi_lo() {
+        let board = parse_cards("2c 5d 8h Ks Kd").unwrap();
+        let players = [
+            parse_cards("Kh Qc Jd Ts").unwrap(),
+            parse_cards("Ah 3c Qd Qs").unwrap(),
+            parse_cards("9c 9d 4h 6h").unwrap(),
+        ];
+        let players = players.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+        let pot = split_pot(&players, &board);
+
+        assert_eq!(pot.high, vec![0]);
+        assert_eq!(pot.low, vec![1]);
+        assert!(best_low(players[0], &board).is_none());
+        assert!(best_low(players[2], &board).is_some());
+    }
+}
@@ -0,0 +1,246 @@
+// outs: the cards still to come that would make a better hand than the one you have now
+//
+// only single cards are counted, so draws that need two running cards (runner-runner)
+// don't show up, and a card counts towards the best hand it makes. that includes every
+// straight and flush draw with three or fewer known cards, since one more card can't finish
+// them: only pairs and other matching ranks can improve until there are four
+
+use crate::card::{find_duplicate, Card, Deck, Hand, HandRanking, RankingRules};
+use crate::equity::choose_count;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outs {
+    current: HandRanking,
+    // every card that makes a better kind of hand, grouped by the hand it makes
+    improvements: BTreeMap<HandRanking, Vec<Card>>,
+    unseen: usize,
+    to_come: usize,
+}
+
+// `known` is the cards you can use, like your hole cards and the board, or the cards held in
+// video poker. `dead` are cards you've seen that can't come, like discards. `to_come` is how
+// many more cards will be dealt, e.g. 2 on the flop in hold'em
+pub fn outs(known: &[Card], dead: &[Card], to_come: usize) -> Outs {
+    outs_with_rules(known, dead, to_come, Deck::new(), &RankingRules::standard())
+}
+
+// like `outs`, for a game dealt from `deck` whose hands are ranked by `rules`, e.g. with
+// jokers in the deck or deuces wild
+pub fn outs_with_rules(
+    known: &[Card],
+    dead: &[Card],
+    to_come: usize,
+    mut deck: Deck,
+    rules: &RankingRules,
+) -> Outs {
+    let seen = [known, dead].concat();
+
+    if let Some(card) = find_duplicate(&seen) {
+        panic!("outs: {:?}appears more than once", card);
+    }
+
+    deck.reset();
+    deck.remove(&seen);
+    let unseen = deck.remaining();
+
+    let current = ranking_of(known, rules);
+    let mut improvements: BTreeMap<HandRanking, Vec<Card>> = BTreeMap::new();
+
+    for &card in unseen {
+        let ranking = ranking_of(&[known, &[card]].concat(), rules);
+
+        if rules.precedence(ranking) > rules.precedence(current) {
+            improvements.entry(ranking).or_default().push(card);
+        }
+    }
+
+    Outs {
+        current,
+        improvements,
+        unseen: unseen.len(),
+        to_come,
+    }
+}
+
+// the best kind of hand these cards make so far. fewer than five cards can only make pairs
+// and other matching ranks, with any wild cards added to the biggest group
+fn ranking_of(cards: &[Card], rules: &RankingRules) -> HandRanking {
+    use HandRanking::*;
+
+    if cards.len() >= rules.hand_size {
+        return *Hand::best_of_with_rules(cards, rules).ranking();
+    }
+
+    let (wild, natural): (Vec<&Card>, Vec<&Card>) =
+        cards.iter().partition(|card| rules.wilds.is_wild(card));
+
+    let mut counts = vec![];
+    for card in &natural {
+        counts.push(natural.iter().filter(|c| c.rank == card.rank).count());
+    }
+    counts.sort_by(|a, b| b.cmp(a));
+
+    match counts.first_mut() {
+        Some(most) => *most += wild.len(),
+        None => counts.push(wild.len()),
+    }
+
+    match counts[..] {
+        [4, ..] => FourOfAKind,
+        [3, ..] => ThreeOfAKind,
+        // each card of a pair is counted, so two pair shows up as four twos
+        [2, 2, 2, 2] => TwoPair,
+        [2, ..] => Pair,
+        _ => HighCard,
+    }
+}
+
+impl Outs {
+    pub fn current(&self) -> HandRanking {
+        self.current
+    }
+
+    // the outs for each better hand, best hand first
+    pub fn improvements(&self) -> impl Iterator<Item = (HandRanking, &[Card])> {
+        self.improvements
+            .iter()
+            .rev()
+            .map(|(&ranking, cards)| (ranking, &cards[..]))
+    }
+
+    pub fn cards(&self, ranking: HandRanking) -> &[Card] {
+        self.improvements
+            .get(&ranking)
+            .map(|cards| &cards[..])
+            .unwrap_or(&[])
+    }
+
+    // how many cards improve the hand at all
+    pub fn count(&self) -> usize {
+        self.improvements.values().map(Vec::len).sum()
+    }
+
+    // the cards that haven't been seen, any of which could come next
+    pub fn unseen(&self) -> usize {
+        self.unseen
+    }
+
+    // the chance, from 0 to 1, that the next card is one of `outs` cards
+    pub fn next_card(&self, outs: usize) -> f64 {
+        outs as f64 / self.unseen as f64
+    }
+
+    // the chance that at least one of `outs` cards comes by the last card, the river in hold'em
+    pub fn by_river(&self, outs: usize) -> f64 {
+        let to_come = self.to_come.min(self.unseen);
+        let misses = choose_count(self.unseen - outs.min(self.unseen), to_come);
+
+        1.0 - misses as f64 / choose_count(self.unseen, to_come) as f64
+    }
+}
+
+/// a line for each better hand with how many outs make it, the chance of hitting one next
+/// and by the river, and the outs themselves
+impl std::fmt::Display for Outs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} now, {} unseen cards, {} to come",
+            self.current, self.unseen, self.to_come
+        )?;
+
+        let mut line = |name: String, outs: usize, cards: &str| {
+            writeln!(
+                f,
+                "{:<16} {:>2} outs {:>7.2}% {:>7.2}%  {}",
+                name,
+                outs,
+                self.next_card(outs) * 100.0,
+                self.by_river(outs) * 100.0,
+                cards
+            )
+        };
+
+        for (ranking, cards) in self.improvements() {
+            let codes = cards.iter().map(Card::code).collect::<Vec<_>>();
+            line(ranking.to_string(), cards.len(), &codes.join(" "))?;
+        }
+
+        line("Total".into(), self.count(), "")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    #[test]
+    fn flush_draw() {
+        let known = parse_cards("Ah Kh 7h 2h 9c").unwrap();
+        let outs = outs(&known, &[], 2);
+
+        assert_eq!(outs.current(), HandRanking::HighCard);
+        assert_eq!(outs.unseen(), 47);
+        assert_eq!(outs.cards(HandRanking::Flush).len(), 9);
+        // pairing any card but the hearts, which make a flush instead
+        assert_eq!(outs.cards(HandRanking::Pair).len(), 14);
+        assert_eq!(outs.count(), 23);
+
+        assert!((outs.next_card(9) - 9.0 / 47.0).abs() < 1e-12);
+        assert!((outs.by_river(9) - (1.0 - 703.0 / 1081.0)).abs() < 1e-12);
+        assert_eq!(outs.by_river(0), 0.0);
+    }
+
+    #[test]
+    fn video_poker_hold() {
+        let held = parse_cards("2h 5h 9h Kh").unwrap();
+        let discard = parse_cards("Qs").unwrap();
+        let outs = outs(&held, &discard, 1);
+
+        assert_eq!(outs.unseen(), 47);
+        assert_eq!(outs.cards(HandRanking::Flush).len(), 9);
+        assert_eq!(outs.cards(HandRanking::Pair).len(), 12);
+        assert!((outs.next_card(9) - outs.by_river(9)).abs() < 1e-12);
+
+        // with no kicker held, three of a kind only gets better on one card by making four
+        let held = parse_cards("8c 8d 8h").unwrap();
+        let outs = super::outs(&held, &[], 2);
+        assert_eq!(outs.current(), HandRanking::ThreeOfAKind);
+        assert_eq!(
+            outs.cards(HandRanking::FourOfAKind),
+            parse_cards("8s").unwrap()
+        );
+        assert_eq!(outs.count(), 1);
+
+        assert_eq!(
+            outs.to_string().lines().next(),
+            Some("Three of a Kind now, 49 unseen cards, 2 to come")
+        );
+    }
+
+    #[test]
+    fn wild_cards() {
+        let deuces = crate::score::Paytable::deuces_wild();
+        let held = parse_cards("2s 7h 7d").unwrap();
+        let outs = outs_with_rules(&held, &[], 2, deuces.deck(), &deuces.rules());
+
+        // the deuce already makes three sevens, and any deuce or seven makes four
+        assert_eq!(outs.current(), HandRanking::ThreeOfAKind);
+        let mut fours = outs.cards(HandRanking::FourOfAKind).to_vec();
+        fours.sort();
+        let mut expected = parse_cards("2h 2c 2d 7s 7c").unwrap();
+        expected.sort();
+        assert_eq!(fours, expected);
+        assert_eq!(outs.count(), 5);
+
+        // the joker is one of the cards that could come
+        let joker_poker = crate::score::Paytable::joker_poker();
+        let held = parse_cards("Kh Kd").unwrap();
+        let outs = outs_with_rules(&held, &[], 3, joker_poker.deck(), &joker_poker.rules());
+        assert_eq!(outs.unseen(), 51);
+        assert!(outs.cards(HandRanking::ThreeOfAKind).contains(&Card::JOKER));
+    }
+}
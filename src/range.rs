@@ -0,0 +1,478 @@
+// hold'em ranges: every starting hand a player might have, written the usual way, e.g.
+// "QQ+, AKs, A5s-A2s, KQo". any part can be weighted by how often it's played, e.g. "AKo:0.5"
+
+use crate::card::{Card, Rank, Suit};
+use crate::combinations::choose;
+use crate::equity::{add, choose_count, live_cards, showdown, Equity, EquityError, Method, Report};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    InvalidHand(String),
+    // weights go from 0 to 1
+    InvalidWeight(String),
+}
+
+impl std::fmt::Display for ParseRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ParseRangeError::*;
+
+        match self {
+            InvalidHand(token) => write!(f, "`{}` is not a hand or range of hands", token),
+            InvalidWeight(token) => write!(f, "invalid weight in `{}`", token),
+        }
+    }
+}
+
+impl std::error::Error for ParseRangeError {}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range {
+    // each pair of hole cards, higher card first, with its weight. no pair appears twice
+    combos: Vec<([Card; 2], f64)>,
+}
+
+impl Range {
+    pub fn new() -> Range {
+        Range::default()
+    }
+
+    // just one known hand, to pit it against other ranges
+    pub fn hole(cards: [Card; 2]) -> Range {
+        let mut range = Range::new();
+        range.insert(cards, 1.0);
+        range
+    }
+
+    // adding a hand that's already in the range changes its weight
+    pub fn insert(&mut self, mut combo: [Card; 2], weight: f64) {
+        combo.sort_by(|a, b| b.cmp(a));
+
+        match self.combos.iter_mut().find(|(c, _)| *c == combo) {
+            Some((_, w)) => *w = weight,
+            None => self.combos.push((combo, weight)),
+        }
+    }
+
+    pub fn combos(&self) -> &[([Card; 2], f64)] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    // take out every hand that uses one of these cards, e.g. ones on the board
+    pub fn without(&self, dead: &[Card]) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|(combo, _)| !combo.iter().any(|card| dead.contains(card)))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// hands separated by commas or whitespace. each one is one of:
+/// - a pair, `QQ`, or `QQ+` for queens or better, or `99-66` for nines down to sixes
+/// - two ranks, suited `AKs`, offsuit `AKo` or either `AK`. `A2s+` counts the second card up to
+///   just under the first, and `A5s-A2s` counts it down from five to two
+/// - two exact cards, `AhKh`
+///
+/// then optionally a weight after a colon, `AKo:0.5`. hands with a weight of 0 are left out
+impl std::str::FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Range, ParseRangeError> {
+        let mut range = Range::new();
+
+        for token in s.split(|c: char| c.is_whitespace() || c == ',') {
+            if token.is_empty() {
+                continue;
+            }
+
+            let (hands, weight) = match token.split_once(':') {
+                None => (token, 1.0),
+                Some((hands, weight)) => match weight.parse::<f64>() {
+                    Ok(weight) if (0.0..=1.0).contains(&weight) => (hands, weight),
+                    _ => return Err(ParseRangeError::InvalidWeight(token.into())),
+                },
+            };
+
+            let combos =
+                parse_hands(hands).ok_or_else(|| ParseRangeError::InvalidHand(token.into()))?;
+
+            for combo in combos {
+                range.insert(combo, weight);
+            }
+        }
+
+        range.combos.retain(|&(_, weight)| weight > 0.0);
+
+        Ok(range)
+    }
+}
+
+// a kind of starting hand, like `AKs`: the higher rank, the lower rank,
+// and whether it's suited, or `None` for either
+type Shape = (Rank, Rank, Option<bool>);
+
+fn parse_hands(s: &str) -> Option<Vec<[Card; 2]>> {
+    if let Some((first, last)) = s.split_once('-') {
+        let (high, low, suited) = parse_shape(first)?;
+        let (last_high, last_low, last_suited) = parse_shape(last)?;
+
+        let shapes = if high == low && last_high == last_low {
+            ranks_between(high, last_high)
+                .map(|rank| (rank, rank, None))
+                .collect::<Vec<_>>()
+        } else if high == last_high && high != low && high != last_low && suited == last_suited {
+            ranks_between(low, last_low)
+                .map(|rank| (high, rank, suited))
+                .collect()
+        } else {
+            return None;
+        };
+
+        return Some(shapes.into_iter().flat_map(shape_combos).collect());
+    }
+
+    if let Some(shape) = s.strip_suffix('+') {
+        let (high, low, suited) = parse_shape(shape)?;
+
+        let shapes = if high == low {
+            ranks_between(high, Rank::Ace)
+                .map(|rank| (rank, rank, None))
+                .collect::<Vec<_>>()
+        } else {
+            let below_high = Rank::try_from(usize::from(high) - 1).ok()?;
+            ranks_between(low, below_high)
+                .map(|rank| (high, rank, suited))
+                .collect()
+        };
+
+        return Some(shapes.into_iter().flat_map(shape_combos).collect());
+    }
+
+    if let Some(shape) = parse_shape(s) {
+        return Some(shape_combos(shape));
+    }
+
+    // two exact cards written together, like `AhKh`
+    if s.len() == 4 && s.is_char_boundary(2) {
+        let (first, second) = s.split_at(2);
+        let first = first.parse::<Card>().ok()?;
+        let second = second.parse::<Card>().ok()?;
+
+        if first == second || first.is_joker() || second.is_joker() {
+            return None;
+        }
+
+        return Some(vec![[first, second]]);
+    }
+
+    None
+}
+
+fn parse_shape(s: &str) -> Option<Shape> {
+    let mut chars = s.chars();
+
+    let mut rank = || match chars.next()?.to_string().parse::<Rank>() {
+        Ok(Rank::Joker) | Err(_) => None,
+        Ok(rank) => Some(rank),
+    };
+    let (first, second) = (rank()?, rank()?);
+
+    let suited = match chars.as_str() {
+        "" => None,
+        "s" | "S" => Some(true),
+        "o" | "O" => Some(false),
+        _ => return None,
+    };
+
+    if first == second && suited.is_some() {
+        return None;
+    }
+
+    Some((first.max(second), first.min(second), suited))
+}
+
+// both ends included, in either order
+fn ranks_between(a: Rank, b: Rank) -> impl Iterator<Item = Rank> {
+    let (low, high) = (usize::from(a.min(b)), usize::from(a.max(b)));
+
+    (low..=high).filter_map(|value| Rank::try_from(value).ok())
+}
+
+fn shape_combos((high, low, suited): Shape) -> Vec<[Card; 2]> {
+    let mut combos = vec![];
+
+    for first in enum_iterator::all::<Suit>() {
+        for second in enum_iterator::all::<Suit>() {
+            let wanted = match suited {
+                _ if high == low => first < second,
+                None => true,
+                Some(suited) => suited == (first == second),
+            };
+
+            if wanted {
+                combos.push([
+                    Card {
+                        rank: high,
+                        suit: first,
+                    },
+                    Card {
+                        rank: low,
+                        suit: second,
+                    },
+                ]);
+            }
+        }
+    }
+
+    combos
+}
+
+// each range's chances against the others, like `equity::equity` but over every hand in each
+// range, weighted by how likely those hands are to be dealt together. a known hand is just a
+// range of one, see `Range::hole`
+pub fn equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+    method: Method,
+) -> Result<Report<f64>, EquityError> {
+    if ranges.len() < 2 {
+        return Err(EquityError::TooFewPlayers(ranges.len()));
+    }
+
+    let known = [board, dead].concat();
+    let live = live_cards(&known, board)?;
+    let missing = 5 - board.len();
+
+    let ranges = ranges
+        .iter()
+        .map(|range| range.without(&known))
+        .collect::<Vec<_>>();
+
+    if let Some(player) = ranges.iter().position(Range::is_empty) {
+        return Err(EquityError::EmptyRange(player));
+    }
+
+    let exact = match method {
+        Method::Exact => true,
+        Method::MonteCarlo { .. } => false,
+        Method::Auto { .. } => {
+            let deals = ranges.iter().fold(1u64, |deals, range| {
+                deals.saturating_mul(range.len() as u64)
+            });
+            let boards = choose_count(live.len().saturating_sub(2 * ranges.len()), missing);
+
+            deals
+                .saturating_mul(boards as u64)
+                .saturating_mul(ranges.len() as u64)
+                <= crate::equity::EXACT_LIMIT
+        }
+    };
+
+    let mut players = vec![Equity::<f64>::default(); ranges.len()];
+
+    if exact {
+        let mut holes = vec![];
+        each_deal(&ranges, &mut holes, 1.0, &mut |holes, weight| {
+            let dealt = holes.concat();
+            let live = live
+                .iter()
+                .filter(|card| !dealt.contains(card))
+                .copied()
+                .collect::<Vec<_>>();
+
+            for rest in choose(&live, missing) {
+                add(&mut players, showdown(holes, board, &rest), weight);
+            }
+        });
+
+        if players[0].boards() == 0.0 {
+            return Err(EquityError::EmptyRange(ranges.len() - 1));
+        }
+    } else {
+        let (trials, seed) = match method {
+            Method::MonteCarlo { trials, seed } | Method::Auto { trials, seed } => (trials, seed),
+            Method::Exact => unreachable!(),
+        };
+
+        let mut prng = prng::Prng64::new(seed);
+
+        for _ in 0..trials {
+            let holes = deal_hands(&ranges, &mut prng)?;
+            let dealt = holes.concat();
+
+            let mut deck = crate::card::Deck::from_cards(live.clone());
+            deck.remove(&dealt);
+            deck.shuffle(&mut prng);
+
+            add(
+                &mut players,
+                showdown(&holes, board, deck.draw(missing).unwrap()),
+                1.0,
+            );
+        }
+    }
+
+    Ok(Report { players, exact })
+}
+
+// call `f` with every way to give each player one hand from their range without any two
+// sharing a card, and the product of their weights
+fn each_deal(
+    ranges: &[Range],
+    holes: &mut Vec<[Card; 2]>,
+    weight: f64,
+    f: &mut impl FnMut(&[[Card; 2]], f64),
+) {
+    let Some(range) = ranges.get(holes.len()) else {
+        f(holes, weight);
+        return;
+    };
+
+    for &(combo, w) in range.combos() {
+        if holes.iter().flatten().any(|card| combo.contains(card)) {
+            continue;
+        }
+
+        holes.push(combo);
+        each_deal(ranges, holes, weight * w, f);
+        holes.pop();
+    }
+}
+
+// one random hand for each player, picked by weight. when hands collide, all of them are dealt
+// again, so that every deal comes up as often as its weights say it should
+fn deal_hands(ranges: &[Range], prng: &mut prng::Prng64) -> Result<Vec<[Card; 2]>, EquityError> {
+    const ATTEMPTS: usize = 10_000;
+
+    for _ in 0..ATTEMPTS {
+        let holes = ranges
+            .iter()
+            .map(|range| pick(range, prng))
+            .collect::<Vec<_>>();
+
+        if crate::card::find_duplicate(&holes.concat()).is_none() {
+            return Ok(holes);
+        }
+    }
+
+    Err(EquityError::EmptyRange(ranges.len() - 1))
+}
+
+fn pick(range: &Range, prng: &mut prng::Prng64) -> [Card; 2] {
+    let total = range.combos.iter().map(|(_, weight)| weight).sum::<f64>();
+
+    // a number from 0 up to but not including 1, from the top 53 bits
+    let unit = (prng.next().unwrap() >> 11) as f64 / (1u64 << 53) as f64;
+    let mut target = unit * total;
+
+    for &(combo, weight) in &range.combos {
+        if target < weight {
+            return combo;
+        }
+        target -= weight;
+    }
+
+    range.combos.last().unwrap().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn range(s: &str) -> Range {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(range("AA").len(), 6);
+        assert_eq!(range("QQ+").len(), 18);
+        assert_eq!(range("99-66").len(), 24);
+        assert_eq!(range("AKs").len(), 4);
+        assert_eq!(range("AKo").len(), 12);
+        assert_eq!(range("KA").len(), 16);
+        assert_eq!(range("A2s+").len(), 48);
+        assert_eq!(range("A5s-A2s").len(), 16);
+        assert_eq!(range("A2s-A5s"), range("A5s-A2s"));
+        assert_eq!(range("QQ+, AKs, A5s-A2s, KQo").len(), 50);
+        assert_eq!(range("AK AKs").len(), 16);
+
+        let cards = parse_cards("Ah Kh").unwrap();
+        assert_eq!(range("AhKh").combos(), &[([cards[0], cards[1]], 1.0)]);
+        assert_eq!(range("KhAh"), range("AhKh"));
+
+        let weighted = range("AKs, AKo:0.5, QQ:0");
+        assert_eq!(weighted.len(), 16);
+        assert_eq!(
+            weighted.combos().iter().map(|(_, w)| w).sum::<f64>(),
+            4.0 + 6.0
+        );
+
+        for bad in [
+            "AKx", "QQs", "A", "AJk", "AK:2", "AK:x", "A5s-K2s", "AKs-AQo", "AhAh",
+        ] {
+            assert!(bad.parse::<Range>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn blockers() {
+        let dead = parse_cards("Ah Kd").unwrap();
+
+        assert_eq!(range("AA").without(&dead).len(), 3);
+        assert_eq!(range("AK").without(&dead).len(), 9);
+        assert!(range("AhKd").without(&dead).is_empty());
+    }
+
+    #[test]
+    fn hand_against_range() {
+        let aces = Range::hole(parse_cards("Ah Ad").unwrap().try_into().unwrap());
+        let board = parse_cards("Kd 7h 2c 3s").unwrap();
+
+        // three ways to hold a set of kings, and two aces left to beat each of them
+        let report = equity(&[aces, range("KK")], &board, &[], Method::Exact).unwrap();
+        assert_eq!(report.players[0].wins(), 3.0 * 2.0);
+        assert_eq!(report.players[0].boards(), 3.0 * 44.0);
+
+        // halving the weight of the hands makes no difference to the percentages
+        let aces = range("AhAd");
+        let half = equity(&[aces, range("KK:0.5")], &board, &[], Method::Exact).unwrap();
+        assert!((half.players[0].win_percent() - report.players[0].win_percent()).abs() < 1e-9);
+
+        assert_eq!(
+            equity(&[range("AA"), range("AdKd")], &board, &[], Method::Exact),
+            Err(EquityError::EmptyRange(1))
+        );
+    }
+
+    #[test]
+    fn range_against_range() {
+        let method = Method::Auto {
+            trials: 10_000,
+            seed: [4, 3, 2, 1],
+        };
+
+        let report = equity(&[range("AA"), range("KK")], &[], &[], method).unwrap();
+        assert!(!report.exact);
+        assert!((report.players[0].equity_percent() - 82.0).abs() < 2.0);
+
+        // on a king-high flop, with every hand that shares a card with the board left out
+        let board = parse_cards("Kh 7c 2d").unwrap();
+        let report = equity(&[range("AA"), range("KK, 77")], &board, &[], method).unwrap();
+        assert!(report.exact);
+        assert!(report.players[0].equity_percent() < 10.0);
+    }
+}
@@ -1,25 +1,937 @@
-use super::card::{Hand, HandRanking, Rank};
+use super::card::{Card, Deck, Hand, HandRanking, Rank, RankingRules, WildCards};
 
+use std::collections::BTreeMap;
+
+// what the original machine paid for a five-coin bet
 pub fn score_hand(hand: &Hand) -> usize {
-    use HandRanking::*;
-
-    match hand.ranking() {
-        HighCard => 0,
-        Pair => {
-            if hand.runs()[0][0].rank >= Rank::Jack {
-                5
-            } else {
-                0
+    Paytable::classic().payout(hand) * 5
+}
+
+// the name of each hand in a paytable file
+const KEYS: [(HandRanking, &str); 12] = [
+    (HandRanking::HighCard, "high_card"),
+    (HandRanking::Pair, "pair"),
+    (HandRanking::TwoPair, "two_pair"),
+    (HandRanking::ThreeOfAKind, "three_of_a_kind"),
+    (HandRanking::Straight, "straight"),
+    (HandRanking::Flush, "flush"),
+    (HandRanking::FullHouse, "full_house"),
+    (HandRanking::FourOfAKind, "four_of_a_kind"),
+    (HandRanking::StraightFlush, "straight_flush"),
+    (HandRanking::RoyalFlush, "royal_flush"),
+    (HandRanking::FiveOfAKind, "five_of_a_kind"),
+    (HandRanking::FlushFive, "flush_five"),
+];
+
+// the most coins a machine takes for one hand
+pub const MAX_COINS: usize = 5;
+
+// the built-in machines, by the name used to pick them on the command line
+pub const PRESETS: [&str; 7] = [
+    "classic",
+    "jacks-or-better",
+    "bonus-poker",
+    "double-bonus",
+    "double-double-bonus",
+    "deuces-wild",
+    "joker-poker",
+];
+
+// which line of a paytable a hand is paid on. most are just the kind of hand, but wild card
+// games pay some hands on lines of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PayLine {
+    Hand(HandRanking),
+    // a royal flush made with a wild card
+    WildRoyalFlush,
+    FourDeuces,
+}
+
+// a four of a kind that pays differently from the rest, picked by the rank of the four and
+// optionally the kicker. both are ranges from the first rank to the second, with aces low,
+// so `(Ace, Four)` is aces through fours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadPay {
+    pub ranks: (Rank, Rank),
+    pub kickers: Option<(Rank, Rank)>,
+    // coins paid per coin bet
+    pub pays: usize,
+}
+
+impl QuadPay {
+    pub fn new(ranks: (Rank, Rank), kickers: Option<(Rank, Rank)>, pays: usize) -> QuadPay {
+        QuadPay {
+            ranks,
+            kickers,
+            pays,
+        }
+    }
+
+    pub fn matches(&self, hand: &Hand) -> bool {
+        if *hand.ranking() != HandRanking::FourOfAKind {
+            return false;
+        }
+
+        let in_range = |rank: Rank, (low, high): (Rank, Rank)| {
+            (ace_low(low)..=ace_low(high)).contains(&ace_low(rank))
+        };
+
+        let kicker_matches = match (self.kickers, hand.kickers().first()) {
+            (None, _) => true,
+            (Some(kickers), Some(kicker)) => in_range(kicker.rank, kickers),
+            (Some(_), None) => false,
+        };
+
+        in_range(hand.runs()[0][0].rank, self.ranks) && kicker_matches
+    }
+
+    // the part of a paytable key after `four_of_a_kind_`, e.g. `A_kicker_2-4`
+    fn key(&self) -> String {
+        let range = |(low, high): (Rank, Rank)| match low == high {
+            true => low.symbol().to_string(),
+            false => format!("{}-{}", low.symbol(), high.symbol()),
+        };
+
+        match self.kickers {
+            Some(kickers) => format!("{}_kicker_{}", range(self.ranks), range(kickers)),
+            None => range(self.ranks),
+        }
+    }
+
+    fn from_key(key: &str, pays: usize) -> Option<QuadPay> {
+        let range = |s: &str| {
+            let (low, high) = s.split_once('-').unwrap_or((s, s));
+            match (low.parse(), high.parse()) {
+                (Ok(Rank::Joker), _) | (_, Ok(Rank::Joker)) => None,
+                (Ok(low), Ok(high)) if ace_low(low) <= ace_low(high) => Some((low, high)),
+                _ => None,
             }
+        };
+
+        let (ranks, kickers) = match key.split_once("_kicker_") {
+            Some((ranks, kickers)) => (range(ranks)?, Some(range(kickers)?)),
+            None => (range(key)?, None),
+        };
+
+        Some(QuadPay::new(ranks, kickers, pays))
+    }
+}
+
+fn ace_low(rank: Rank) -> usize {
+    match rank {
+        Rank::Ace => 1,
+        _ => rank as usize,
+    }
+}
+
+// what a machine pays for each hand, per coin bet
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Paytable {
+    name: String,
+    // hands that aren't listed pay nothing
+    pays: BTreeMap<HandRanking, usize>,
+    // the lowest pair that pays, e.g. jacks in jacks or better
+    min_pair: Rank,
+    // fours of a kind that don't pay the usual `four_of_a_kind`
+    quads: Vec<QuadPay>,
+    // what a royal flush pays in all when `MAX_COINS` are bet, instead of five times its usual pay
+    max_coin_royal: Option<usize>,
+    // how many jokers are added to the deck
+    jokers: usize,
+    // a rank that's wild on top of jokers, e.g. deuces
+    wild: Option<Rank>,
+    // with these left out, wild royals pay as royal flushes and four deuces as whatever they make
+    wild_royal_flush: Option<usize>,
+    four_deuces: Option<usize>,
+}
+
+impl Paytable {
+    pub fn new(name: &str, min_pair: Rank) -> Paytable {
+        Paytable {
+            name: name.into(),
+            pays: BTreeMap::new(),
+            min_pair,
+            quads: vec![],
+            max_coin_royal: None,
+            jokers: 0,
+            wild: None,
+            wild_royal_flush: None,
+            four_deuces: None,
+        }
+    }
+
+    fn with_pays(name: &str, min_pair: Rank, pays: &[(HandRanking, usize)]) -> Paytable {
+        let mut table = Paytable::new(name, min_pair);
+        for &(ranking, coins) in pays {
+            table.set(ranking, coins);
         }
-        TwoPair => 10,
-        ThreeOfAKind => 15,
-        Straight => 20,
-        Flush => 25,
-        FullHouse => 40,
-        FourOfAKind => 125,
-        StraightFlush => 250,
-        RoyalFlush => 3000,
-        FiveOfAKind => 8000,
+        table
+    }
+
+    fn with_quads(mut self, quads: &[QuadPay]) -> Paytable {
+        self.quads.extend_from_slice(quads);
+        self
+    }
+
+    // the usual 4000 coins for a royal at max bet
+    fn with_royal_bonus(mut self) -> Paytable {
+        self.max_coin_royal = Some(4000);
+        self
+    }
+
+    // the table the game has always used, with no bonus for betting max coins
+    pub fn classic() -> Paytable {
+        use HandRanking::*;
+
+        Paytable::with_pays(
+            "Classic",
+            Rank::Jack,
+            &[
+                (Pair, 1),
+                (TwoPair, 2),
+                (ThreeOfAKind, 3),
+                (Straight, 4),
+                (Flush, 5),
+                (FullHouse, 8),
+                (FourOfAKind, 25),
+                (StraightFlush, 50),
+                (RoyalFlush, 600),
+                (FiveOfAKind, 1600),
+                (FlushFive, 1600),
+            ],
+        )
+    }
+
+    // "9/6", for what the full house and flush pay
+    pub fn jacks_or_better() -> Paytable {
+        use HandRanking::*;
+
+        Paytable::with_pays(
+            "9/6 Jacks or Better",
+            Rank::Jack,
+            &[
+                (Pair, 1),
+                (TwoPair, 2),
+                (ThreeOfAKind, 3),
+                (Straight, 4),
+                (Flush, 6),
+                (FullHouse, 9),
+                (FourOfAKind, 25),
+                (StraightFlush, 50),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_royal_bonus()
+    }
+
+    // aces and small fours of a kind pay extra
+    pub fn bonus_poker() -> Paytable {
+        use HandRanking::*;
+        use Rank::{Ace, Four, Two};
+
+        Paytable::with_pays(
+            "8/5 Bonus Poker",
+            Rank::Jack,
+            &[
+                (Pair, 1),
+                (TwoPair, 2),
+                (ThreeOfAKind, 3),
+                (Straight, 4),
+                (Flush, 5),
+                (FullHouse, 8),
+                (FourOfAKind, 25),
+                (StraightFlush, 50),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_quads(&[
+            QuadPay::new((Ace, Ace), None, 80),
+            QuadPay::new((Two, Four), None, 40),
+        ])
+        .with_royal_bonus()
+    }
+
+    // two pair only gets your bet back, to pay for the bigger four of a kind
+    pub fn double_bonus() -> Paytable {
+        use HandRanking::*;
+        use Rank::{Ace, Four, Two};
+
+        Paytable::with_pays(
+            "10/7 Double Bonus",
+            Rank::Jack,
+            &[
+                (Pair, 1),
+                (TwoPair, 1),
+                (ThreeOfAKind, 3),
+                (Straight, 5),
+                (Flush, 7),
+                (FullHouse, 10),
+                (FourOfAKind, 50),
+                (StraightFlush, 50),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_quads(&[
+            QuadPay::new((Ace, Ace), None, 160),
+            QuadPay::new((Two, Four), None, 80),
+        ])
+        .with_royal_bonus()
+    }
+
+    // double bonus, plus more again for the right kicker with aces or small fours
+    pub fn double_double_bonus() -> Paytable {
+        use HandRanking::*;
+        use Rank::{Ace, Four, Two};
+
+        Paytable::with_pays(
+            "9/6 Double Double Bonus",
+            Rank::Jack,
+            &[
+                (Pair, 1),
+                (TwoPair, 1),
+                (ThreeOfAKind, 3),
+                (Straight, 4),
+                (Flush, 6),
+                (FullHouse, 9),
+                (FourOfAKind, 50),
+                (StraightFlush, 50),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_quads(&[
+            QuadPay::new((Ace, Ace), None, 160),
+            QuadPay::new((Two, Four), None, 80),
+            QuadPay::new((Ace, Ace), Some((Two, Four)), 400),
+            QuadPay::new((Two, Four), Some((Ace, Four)), 160),
+        ])
+        .with_royal_bonus()
+    }
+
+    // "full pay", with all the deuces wild and nothing paid below three of a kind
+    pub fn deuces_wild() -> Paytable {
+        use HandRanking::*;
+
+        let mut table = Paytable::with_pays(
+            "Full Pay Deuces Wild",
+            Rank::Jack,
+            &[
+                (ThreeOfAKind, 1),
+                (Straight, 2),
+                (Flush, 2),
+                (FullHouse, 3),
+                (FourOfAKind, 5),
+                (StraightFlush, 9),
+                (FiveOfAKind, 15),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_royal_bonus();
+
+        table.wild = Some(Rank::Two);
+        table.wild_royal_flush = Some(25);
+        table.four_deuces = Some(200);
+        table
+    }
+
+    // "20/7/5" kings or better, with one joker in the deck
+    pub fn joker_poker() -> Paytable {
+        use HandRanking::*;
+
+        let mut table = Paytable::with_pays(
+            "20/7/5 Joker Poker",
+            Rank::King,
+            &[
+                (Pair, 1),
+                (TwoPair, 1),
+                (ThreeOfAKind, 2),
+                (Straight, 3),
+                (Flush, 5),
+                (FullHouse, 7),
+                (FourOfAKind, 20),
+                (StraightFlush, 50),
+                (FiveOfAKind, 200),
+                (RoyalFlush, 250),
+            ],
+        )
+        .with_royal_bonus();
+
+        table.jokers = 1;
+        table.wild_royal_flush = Some(100);
+        table
+    }
+
+    // every built-in table, in the order of `PRESETS`
+    pub fn presets() -> Vec<Paytable> {
+        PRESETS
+            .iter()
+            .map(|name| Paytable::preset(name).unwrap())
+            .collect()
+    }
+
+    // one of `PRESETS`, e.g. "jacks-or-better"
+    pub fn preset(name: &str) -> Option<Paytable> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "classic" => Paytable::classic(),
+            "jacks-or-better" => Paytable::jacks_or_better(),
+            "bonus-poker" => Paytable::bonus_poker(),
+            "double-bonus" => Paytable::double_bonus(),
+            "double-double-bonus" => Paytable::double_double_bonus(),
+            "deuces-wild" => Paytable::deuces_wild(),
+            "joker-poker" => Paytable::joker_poker(),
+            _ => return None,
+        })
+    }
+
+    // read a paytable file, see `Paytable::from_str` for the format
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Paytable, Box<dyn std::error::Error>> {
+        Ok(std::fs::read_to_string(path)?.parse()?)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn min_pair(&self) -> Rank {
+        self.min_pair
+    }
+
+    pub fn quads(&self) -> &[QuadPay] {
+        &self.quads
+    }
+
+    // when more than one of these matches a hand, it pays whichever pays most
+    pub fn add_quads(&mut self, quads: QuadPay) {
+        self.quads.push(quads);
+    }
+
+    pub fn max_coin_royal(&self) -> Option<usize> {
+        self.max_coin_royal
+    }
+
+    // `Some(0)` is the same as no bonus
+    pub fn set_max_coin_royal(&mut self, coins: Option<usize>) {
+        self.max_coin_royal = coins.filter(|&coins| coins > 0);
+    }
+
+    pub fn jokers(&self) -> usize {
+        self.jokers
+    }
+
+    pub fn set_jokers(&mut self, jokers: usize) {
+        assert!(jokers <= 4, "Paytable::set_jokers: at most four jokers");
+        self.jokers = jokers;
+    }
+
+    // a fresh deck for this machine, with its jokers
+    pub fn deck(&self) -> Deck {
+        Deck::with_jokers(self.jokers)
+    }
+
+    pub fn wild(&self) -> Option<Rank> {
+        self.wild
+    }
+
+    pub fn set_wild(&mut self, wild: Option<Rank>) {
+        self.wild = wild;
+    }
+
+    pub fn wild_royal_flush(&self) -> Option<usize> {
+        self.wild_royal_flush
+    }
+
+    pub fn set_wild_royal_flush(&mut self, coins: Option<usize>) {
+        self.wild_royal_flush = coins;
+    }
+
+    pub fn four_deuces(&self) -> Option<usize> {
+        self.four_deuces
+    }
+
+    pub fn set_four_deuces(&mut self, coins: Option<usize>) {
+        self.four_deuces = coins;
+    }
+
+    // how this machine ranks hands, with its wild cards
+    pub fn rules(&self) -> RankingRules {
+        let wilds = self.wild.map(|rank| vec![rank]).unwrap_or_default();
+
+        let mut rules = RankingRules::standard();
+        rules.wilds = WildCards::ranks(&wilds);
+        rules
+    }
+
+    // the hand these cards make on this machine. wild cards stand in for whatever pays the
+    // most, so e.g. a wild royal beats five of a kind when it pays more
+    pub fn hand(&self, cards: &[Card]) -> Hand {
+        Hand::with_rules_by(cards, &self.rules(), |hand| self.payout(hand))
+    }
+
+    // the line a hand made with `Paytable::hand` is paid on
+    pub fn pay_line(&self, hand: &Hand) -> PayLine {
+        let ranking = *hand.ranking();
+        let wilds = hand.substitutions().len();
+        // jokers don't count towards four deuces
+        let deuces = hand
+            .substitutions()
+            .iter()
+            .filter(|(card, _)| Some(card.rank) == self.wild)
+            .count();
+
+        if self.four_deuces.is_some() && deuces == 4 {
+            PayLine::FourDeuces
+        } else if self.wild_royal_flush.is_some() && ranking == HandRanking::RoyalFlush && wilds > 0
+        {
+            PayLine::WildRoyalFlush
+        } else {
+            PayLine::Hand(ranking)
+        }
+    }
+
+    // coins paid per coin bet
+    pub fn pays(&self, ranking: HandRanking) -> usize {
+        self.pays.get(&ranking).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, ranking: HandRanking, coins: usize) {
+        match coins {
+            0 => self.pays.remove(&ranking),
+            _ => self.pays.insert(ranking, coins),
+        };
+    }
+
+    // coins paid per coin bet for this hand
+    pub fn payout(&self, hand: &Hand) -> usize {
+        let ranking = match self.pay_line(hand) {
+            PayLine::Hand(ranking) => ranking,
+            PayLine::WildRoyalFlush => return self.wild_royal_flush.unwrap_or(0),
+            PayLine::FourDeuces => return self.four_deuces.unwrap_or(0),
+        };
+
+        if ranking == HandRanking::Pair && hand.runs()[0][0].rank < self.min_pair {
+            return 0;
+        }
+
+        let quads = self.quads.iter().filter(|quads| quads.matches(hand));
+
+        quads
+            .map(|quads| quads.pays)
+            .max()
+            .unwrap_or_else(|| self.pays(ranking))
+    }
+
+    // everything paid for this hand when `coins` are bet, from 1 to `MAX_COINS`
+    pub fn payout_for_bet(&self, hand: &Hand, coins: usize) -> usize {
+        assert!(
+            (1..=MAX_COINS).contains(&coins),
+            "payout_for_bet: can't bet {} coins",
+            coins
+        );
+
+        match self.max_coin_royal {
+            Some(bonus)
+                if coins == MAX_COINS
+                    && self.pay_line(hand) == PayLine::Hand(HandRanking::RoyalFlush) =>
+            {
+                bonus
+            }
+            _ => self.payout(hand) * coins,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaytableError {
+    // the line number, counting from 1
+    Syntax(usize),
+    UnknownKey(String),
+    InvalidValue(String),
+}
+
+impl std::fmt::Display for PaytableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use PaytableError::*;
+
+        match self {
+            Syntax(line) => write!(f, "line {}: expected `key = value`", line),
+            UnknownKey(key) => write!(f, "unknown key `{}`", key),
+            InvalidValue(key) => write!(f, "invalid value for `{}`", key),
+        }
+    }
+}
+
+impl std::error::Error for PaytableError {}
+
+/// one `key = value` per line, which is also valid TOML. `#` starts a comment
+///
+/// ```text
+/// name = "9/6 Jacks or Better"
+/// min_pair = "J"
+/// pair = 1
+/// full_house = 9
+/// ```
+///
+/// the hands are `high_card`, `pair`, `two_pair`, `three_of_a_kind`, `straight`, `flush`,
+/// `full_house`, `four_of_a_kind`, `straight_flush`, `royal_flush`, `five_of_a_kind` and
+/// `flush_five`. any that are left out pay nothing. `max_coin_royal` is what a royal flush
+/// pays in all when the most coins are bet, if it's more than usual
+///
+/// a four of a kind can pay differently by rank, and by kicker, with keys like
+/// `four_of_a_kind_A`, `four_of_a_kind_2-4` or `four_of_a_kind_A_kicker_2-4`. aces are low
+/// in a range of ranks
+///
+/// `jokers = 1` adds a joker to the deck. jokers are always wild, and `wild = "2"` makes a rank
+/// wild too. a royal flush made with a wild card pays `wild_royal_flush`, and four of the wild
+/// rank pay `four_deuces`. without those keys, they pay as the hands they make
+impl std::str::FromStr for Paytable {
+    type Err = PaytableError;
+
+    fn from_str(s: &str) -> Result<Paytable, PaytableError> {
+        let mut table = Paytable::new("Custom", Rank::Jack);
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(PaytableError::Syntax(number + 1))?;
+            let key = key.trim();
+            let value = value_of(value).ok_or_else(|| PaytableError::InvalidValue(key.into()))?;
+            let invalid = || PaytableError::InvalidValue(key.into());
+
+            match key {
+                "name" => table.name = value.into(),
+                "min_pair" => match value.parse::<Rank>() {
+                    Ok(Rank::Joker) | Err(_) => return Err(invalid()),
+                    Ok(rank) => table.min_pair = rank,
+                },
+                "max_coin_royal" => {
+                    table.set_max_coin_royal(Some(value.parse().map_err(|_| invalid())?))
+                }
+                "jokers" => match value.parse() {
+                    Ok(jokers) if jokers <= 4 => table.jokers = jokers,
+                    _ => return Err(invalid()),
+                },
+                "wild" => match value.parse::<Rank>() {
+                    Ok(Rank::Joker) | Err(_) => return Err(invalid()),
+                    Ok(rank) => table.wild = Some(rank),
+                },
+                "wild_royal_flush" => {
+                    table.wild_royal_flush = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "four_deuces" => table.four_deuces = Some(value.parse().map_err(|_| invalid())?),
+                _ if key.starts_with("four_of_a_kind_") => {
+                    let pays = value.parse().map_err(|_| invalid())?;
+                    let quads = QuadPay::from_key(&key["four_of_a_kind_".len()..], pays)
+                        .ok_or_else(|| PaytableError::UnknownKey(key.into()))?;
+                    table.add_quads(quads);
+                }
+                _ => {
+                    let &(ranking, _) = KEYS
+                        .iter()
+                        .find(|(_, name)| *name == key)
+                        .ok_or_else(|| PaytableError::UnknownKey(key.into()))?;
+                    table.set(ranking, value.parse().map_err(|_| invalid())?);
+                }
+            }
+        }
+
+        Ok(table)
+    }
+}
+
+// a value with any quotes and trailing comment taken off
+fn value_of(value: &str) -> Option<&str> {
+    let value = value.trim();
+
+    match value.strip_prefix('"') {
+        Some(quoted) => {
+            let (value, rest) = quoted.split_once('"')?;
+            let rest = rest.trim();
+            (rest.is_empty() || rest.starts_with('#')).then_some(value)
+        }
+        None => value.split('#').next().map(str::trim),
+    }
+}
+
+/// the same format `Paytable::from_str` reads
+impl std::fmt::Display for Paytable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "name = \"{}\"", self.name)?;
+        writeln!(f, "min_pair = \"{}\"", self.min_pair.symbol())?;
+
+        if let Some(coins) = self.max_coin_royal {
+            writeln!(f, "max_coin_royal = {}", coins)?;
+        }
+
+        if self.jokers > 0 {
+            writeln!(f, "jokers = {}", self.jokers)?;
+        }
+
+        if let Some(rank) = self.wild {
+            writeln!(f, "wild = \"{}\"", rank.symbol())?;
+        }
+
+        if let Some(coins) = self.wild_royal_flush {
+            writeln!(f, "wild_royal_flush = {}", coins)?;
+        }
+
+        if let Some(coins) = self.four_deuces {
+            writeln!(f, "four_deuces = {}", coins)?;
+        }
+
+        for (ranking, coins) in &self.pays {
+            let (_, key) = KEYS.iter().find(|(r, _)| r == ranking).unwrap();
+            writeln!(f, "{} = {}", key, coins)?;
+        }
+
+        for quads in &self.quads {
+            writeln!(f, "four_of_a_kind_{} = {}", quads.key(), quads.pays)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn payouts() {
+        let table = Paytable::jacks_or_better();
+
+        assert_eq!(table.payout(&hand("Jh Jd 2c 9s Ah")), 1);
+        assert_eq!(table.payout(&hand("Th Td 2c 9s Ah")), 0);
+        assert_eq!(table.payout(&hand("6h 6d 6c Ts Th")), 9);
+        assert_eq!(table.payout(&hand("Ah 9h Kh 4h 7h")), 6);
+        assert_eq!(table.payout(&hand("Qh 9d 7c 4s 3h")), 0);
+
+        let mut tens = table.clone();
+        tens.min_pair = Rank::Ten;
+        assert_eq!(tens.payout(&hand("Th Td 2c 9s Ah")), 1);
+
+        // the old hard-coded scores, for five coins
+        assert_eq!(score_hand(&hand("Ts Js Qs Ks As")), 3000);
+        assert_eq!(score_hand(&hand("9h 9d 9c 9s Ah")), 125);
+        assert_eq!(score_hand(&hand("Jh Jd 2c 9s Ah")), 5);
+    }
+
+    #[test]
+    fn bets() {
+        let table = Paytable::jacks_or_better();
+        let royal = hand("Ts Js Qs Ks As");
+
+        assert_eq!(table.payout_for_bet(&hand("6h 6d 6c Ts Th"), 3), 27);
+        assert_eq!(table.payout_for_bet(&royal, 4), 1000);
+        assert_eq!(table.payout_for_bet(&royal, MAX_COINS), 4000);
+
+        // the classic table has no bonus, so a royal is still 600 a coin
+        assert_eq!(Paytable::classic().payout_for_bet(&royal, MAX_COINS), 3000);
+
+        let table = "royal_flush = 250\nmax_coin_royal = 0"
+            .parse::<Paytable>()
+            .unwrap();
+        assert_eq!(table.max_coin_royal(), None);
+        assert_eq!(table.payout_for_bet(&royal, MAX_COINS), 1250);
+    }
+
+    #[test]
+    fn quads() {
+        let bonus = Paytable::bonus_poker();
+        assert_eq!(bonus.payout(&hand("Ah Ad Ac As 9h")), 80);
+        assert_eq!(bonus.payout(&hand("3h 3d 3c 3s 9h")), 40);
+        assert_eq!(bonus.payout(&hand("Kh Kd Kc Ks 9h")), 25);
+
+        let ddb = Paytable::double_double_bonus();
+        assert_eq!(ddb.payout(&hand("Ah Ad Ac As 2h")), 400);
+        assert_eq!(ddb.payout(&hand("Ah Ad Ac As 5h")), 160);
+        assert_eq!(ddb.payout(&hand("4h 4d 4c 4s Ah")), 160);
+        assert_eq!(ddb.payout(&hand("4h 4d 4c 4s 5h")), 80);
+        assert_eq!(ddb.payout(&hand("5h 5d 5c 5s 2h")), 50);
+
+        let table = "four_of_a_kind = 25\nfour_of_a_kind_a-4_kicker_K = 99"
+            .parse::<Paytable>()
+            .unwrap();
+        assert_eq!(table.payout(&hand("2h 2d 2c 2s Kh")), 99);
+        assert_eq!(table.payout(&hand("2h 2d 2c 2s Qh")), 25);
+
+        assert_eq!(
+            "four_of_a_kind_K-A = 1".parse::<Paytable>(),
+            Err(PaytableError::UnknownKey("four_of_a_kind_K-A".into()))
+        );
+    }
+
+    #[test]
+    fn deuces_wild() {
+        let table = Paytable::deuces_wild();
+        let line = |cards: &str| {
+            let hand = table.hand(&parse_cards(cards).unwrap());
+            (table.pay_line(&hand), table.payout(&hand))
+        };
+
+        assert_eq!(
+            line("Ts Js Qs Ks As"),
+            (PayLine::Hand(HandRanking::RoyalFlush), 250)
+        );
+        assert_eq!(line("Ts Js 2h Ks As"), (PayLine::WildRoyalFlush, 25));
+        assert_eq!(line("2s 2h 2d 2c 9h"), (PayLine::FourDeuces, 200));
+        assert_eq!(
+            line("2s 2h 9d 9c 9h"),
+            (PayLine::Hand(HandRanking::FiveOfAKind), 15)
+        );
+        assert_eq!(
+            line("2s 7h 9d 9c Kh"),
+            (PayLine::Hand(HandRanking::ThreeOfAKind), 1)
+        );
+        assert_eq!(
+            line("Ks 7h 9d 9c Kh"),
+            (PayLine::Hand(HandRanking::TwoPair), 0)
+        );
+
+        // only a natural royal gets the max bet bonus
+        let hand = table.hand(&parse_cards("Ts Js 2h Ks As").unwrap());
+        assert_eq!(table.payout_for_bet(&hand, MAX_COINS), 125);
+
+        // with a joker in the deck too, it takes all four deuces
+        let mut with_joker = table.clone();
+        with_joker.set_jokers(1);
+        let hand = with_joker.hand(&parse_cards("2s 2h 2d Jk 9h").unwrap());
+        assert_eq!(
+            with_joker.pay_line(&hand),
+            PayLine::Hand(HandRanking::FiveOfAKind)
+        );
+
+        // five aces rank higher, but the royal pays more
+        let hand = with_joker.hand(&parse_cards("2s 2h 2d Jk Ah").unwrap());
+        assert_eq!(
+            (with_joker.pay_line(&hand), with_joker.payout(&hand)),
+            (PayLine::WildRoyalFlush, 25)
+        );
+
+        // without its own lines, a table pays what the deuces make
+        let mut plain = table.clone();
+        plain.set_four_deuces(None);
+        let hand = plain.hand(&parse_cards("2s 2h 2d 2c 9h").unwrap());
+        assert_eq!(plain.payout(&hand), 15);
+    }
+
+    #[test]
+    fn joker_poker() {
+        let table = Paytable::joker_poker();
+        assert_eq!(table.deck().len(), 53);
+
+        let line = |cards: &str| {
+            let hand = table.hand(&parse_cards(cards).unwrap());
+            (table.pay_line(&hand), table.payout(&hand))
+        };
+
+        assert_eq!(
+            line("Jk As Ah Ac Ad"),
+            (PayLine::Hand(HandRanking::FiveOfAKind), 200)
+        );
+        assert_eq!(line("Ts Js Jk Ks As"), (PayLine::WildRoyalFlush, 100));
+        assert_eq!(
+            line("Ts Js Qs Ks As"),
+            (PayLine::Hand(HandRanking::RoyalFlush), 250)
+        );
+        assert_eq!(
+            line("Jk 3h 7d 9c Kh"),
+            (PayLine::Hand(HandRanking::Pair), 1)
+        );
+        assert_eq!(
+            line("Qs Qh 7d 9c 3h"),
+            (PayLine::Hand(HandRanking::Pair), 0)
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let table = "
+            # a machine that's generous with flushes
+            name = \"Loose #7\"
+            min_pair = \"Q\"
+            pair = 1
+            flush = 10   # up from 6
+            royal_flush = 800
+        "
+        .parse::<Paytable>()
+        .unwrap();
+
+        assert_eq!(table.name(), "Loose #7");
+        assert_eq!(table.min_pair(), Rank::Queen);
+        assert_eq!(table.pays(HandRanking::Flush), 10);
+        assert_eq!(table.pays(HandRanking::Straight), 0);
+
+        assert_eq!(
+            "pair = 1\nfive_of_a_kind_or_so = 2".parse::<Paytable>(),
+            Err(PaytableError::UnknownKey("five_of_a_kind_or_so".into()))
+        );
+        assert_eq!(
+            "pair = lots".parse::<Paytable>(),
+            Err(PaytableError::InvalidValue("pair".into()))
+        );
+        assert_eq!(
+            "\npair 1".parse::<Paytable>(),
+            Err(PaytableError::Syntax(2))
+        );
+    }
+
+    #[test]
+    fn presets_pay_what_they_say() {
+        use HandRanking::*;
+
+        // the numbers in a name like "9/6" are what the full house and flush pay, and
+        // "20/7/5" puts four of a kind first
+        for table in Paytable::presets() {
+            let numbers = table.name().split(' ').next().unwrap().split('/');
+            let numbers = numbers.collect::<Vec<_>>();
+            let lines: &[HandRanking] = match numbers.len() {
+                2 => &[FullHouse, Flush],
+                3 => &[FourOfAKind, FullHouse, Flush],
+                _ => &[],
+            };
+
+            for (ranking, number) in lines.iter().zip(numbers) {
+                assert_eq!(table.pays(*ranking).to_string(), number, "{}", table.name());
+            }
+        }
+
+        // the bonus games pay more for aces and small fours of a kind than the rest
+        for table in [
+            Paytable::bonus_poker(),
+            Paytable::double_bonus(),
+            Paytable::double_double_bonus(),
+        ] {
+            let quads = table.payout(&hand("Kh Kd Kc Ks 9h"));
+            assert!(table.payout(&hand("Ah Ad Ac As 9h")) > quads);
+            assert!(table.payout(&hand("3h 3d 3c 3s 9h")) > quads);
+        }
+    }
+
+    #[test]
+    fn presets_round_trip() {
+        for table in Paytable::presets() {
+            assert_eq!(table.to_string().parse::<Paytable>(), Ok(table));
+        }
+
+        assert_eq!(
+            Paytable::preset("Jacks-or-Better"),
+            Some(Paytable::jacks_or_better())
+        );
+        assert_eq!(Paytable::preset("video-keno"), None);
+
+        let file = include_str!("../paytables/9-6-jacks-or-better.toml");
+        assert_eq!(file.parse(), Ok(Paytable::jacks_or_better()));
     }
 }
@@ -0,0 +1,193 @@
+// serde support, behind the `serde` feature
+//
+// cards are written as the short codes from `Card::code`, e.g. `"Ah"`, so that saved games and
+// hand histories stay readable and don't depend on the order of the enums
+
+use crate::card::{find_duplicate, Card, Deck, Hand, HandRanking, Rank, Suit};
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+fn from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
+
+/// e.g. `"T"`, or `"Jk"` for the joker
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.symbol())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rank, D::Error> {
+        from_str(deserializer)
+    }
+}
+
+/// one of `"s" "h" "c" "d"`
+impl Serialize for Suit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.letter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Suit, D::Error> {
+        from_str(deserializer)
+    }
+}
+
+/// e.g. `"Ah"`, see `Card::code`
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        from_str(deserializer)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HandRecord {
+    cards: Vec<Card>,
+    // only written for people reading the file. it's worked out again from the cards
+    #[serde(default, skip_deserializing)]
+    ranking: Option<HandRanking>,
+}
+
+/// e.g. `{"cards": ["Kh", "7c", "Ks", "7d", "Ah"], "ranking": "TwoPair"}`. hands are read back
+/// with `Hand::new`, so jokers are the only wild cards
+impl Serialize for Hand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HandRecord {
+            cards: self.cards().to_vec(),
+            ranking: Some(*self.ranking()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Hand, D::Error> {
+        let record = HandRecord::deserialize(deserializer)?;
+
+        if record.cards.len() != 5 {
+            return Err(D::Error::invalid_length(record.cards.len(), &"five cards"));
+        }
+
+        if let Some(card) = find_duplicate(&record.cards) {
+            return Err(D::Error::custom(format!(
+                "{} appears more than once",
+                card.code()
+            )));
+        }
+
+        Ok(Hand::new(&record.cards))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeckRecord {
+    // every card, in order, including those already dealt
+    cards: Vec<Card>,
+    // how many have been dealt
+    dealt: usize,
+}
+
+/// e.g. `{"cards": ["Ah", "7c", ...], "dealt": 5}`
+impl Serialize for Deck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DeckRecord {
+            cards: [self.dealt(), self.remaining()].concat(),
+            dealt: self.dealt().len(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Deck {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Deck, D::Error> {
+        let record = DeckRecord::deserialize(deserializer)?;
+
+        if record.dealt > record.cards.len() {
+            return Err(D::Error::custom(format!(
+                "{} cards dealt from a deck of {}",
+                record.dealt,
+                record.cards.len()
+            )));
+        }
+
+        let mut deck = Deck::from_cards(record.cards);
+        deck.draw(record.dealt);
+
+        Ok(deck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    #[test]
+    fn cards() {
+        let cards = parse_cards("Ah Td 2c 9s Jk").unwrap();
+        let json = serde_json::to_string(&cards).unwrap();
+
+        assert_eq!(json, r#"["Ah","Td","2c","9s","Jks"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Card>>(&json).unwrap(), cards);
+        assert_eq!(
+            serde_json::from_str::<Card>(r#""Jkh""#).unwrap(),
+            Card::joker(Suit::Heart)
+        );
+        assert!(serde_json::from_str::<Card>(r#""Zz""#).is_err());
+
+        assert_eq!(serde_json::to_string(&Rank::Ten).unwrap(), r#""T""#);
+        assert_eq!(serde_json::to_string(&Suit::Club).unwrap(), r#""c""#);
+        assert_eq!(
+            serde_json::from_str::<Rank>(r#""Jk""#).unwrap(),
+            Rank::Joker
+        );
+    }
+
+    #[test]
+    fn hands() {
+        let hand = "Kh 7c Ks 7d Ah".parse::<Hand>().unwrap();
+        let json = serde_json::to_string(&hand).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"cards":["Kh","7c","Ks","7d","Ah"],"ranking":"TwoPair"}"#
+        );
+        assert!(serde_json::from_str::<Hand>(&json).unwrap() == hand);
+        assert!(serde_json::from_str::<Hand>(r#"{"cards":["Kh","7c","Ks","7d"]}"#).is_err());
+        assert!(serde_json::from_str::<Hand>(r#"{"cards":["Kh","Kh","Ks","7d","Ah"]}"#).is_err());
+    }
+
+    #[test]
+    fn game_state() {
+        let mut state = crate::GameState::new();
+        state.deck.draw(5);
+        state.score = 42;
+
+        let json = serde_json::to_string(&state).unwrap();
+        let loaded = serde_json::from_str::<crate::GameState>(&json).unwrap();
+
+        assert_eq!(loaded.score, 42);
+        assert_eq!(loaded.high_score, state.high_score);
+        assert_eq!(loaded.deck.dealt(), state.deck.dealt());
+        assert_eq!(loaded.deck.remaining(), state.deck.remaining());
+        assert!(serde_json::from_str::<Deck>(r#"{"cards":["Ah"],"dealt":2}"#).is_err());
+    }
+}
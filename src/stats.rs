@@ -0,0 +1,156 @@
+// how often each kind of hand comes up, either by dealing every possible hand once or by
+// sampling shuffled decks
+
+use crate::card::{Deck, Hand, HandRanking, Rank};
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frequencies {
+    counts: BTreeMap<HandRanking, u64>,
+    // hands that would pay on a jacks-or-better machine, a pair of jacks or anything better
+    jacks_or_better: u64,
+    total: u64,
+}
+
+impl Frequencies {
+    pub fn new() -> Frequencies {
+        Frequencies::default()
+    }
+
+    // deal every five-card hand from a fresh deck exactly once, 2,598,960 of them
+    pub fn exhaustive() -> Frequencies {
+        let mut frequencies = Frequencies::new();
+
+        for five in Deck::new().combinations::<5>() {
+            frequencies.add(&Hand::new(&five));
+        }
+
+        frequencies
+    }
+
+    // deal the first five cards of `hands` shuffled decks
+    pub fn sample(prng: &mut prng::Prng64, hands: u64) -> Frequencies {
+        let mut frequencies = Frequencies::new();
+        let mut deck = Deck::new();
+
+        for _ in 0..hands {
+            deck.shuffle(prng);
+            let five = deck.draw(5).expect("a fresh deck has at least five cards");
+            frequencies.add(&Hand::new(five));
+        }
+
+        frequencies
+    }
+
+    pub fn add(&mut self, hand: &Hand) {
+        let ranking = *hand.ranking();
+
+        *self.counts.entry(ranking).or_insert(0) += 1;
+
+        let is_high_pair = ranking == HandRanking::Pair && hand.runs()[0][0].rank >= Rank::Jack;
+        if is_high_pair || ranking > HandRanking::Pair {
+            self.jacks_or_better += 1;
+        }
+
+        self.total += 1;
+    }
+
+    pub fn count(&self, ranking: HandRanking) -> u64 {
+        self.counts.get(&ranking).copied().unwrap_or(0)
+    }
+
+    pub fn jacks_or_better(&self) -> u64 {
+        self.jacks_or_better
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    // from 0 to 1
+    pub fn probability(&self, ranking: HandRanking) -> f64 {
+        self.count(ranking) as f64 / self.total as f64
+    }
+}
+
+/// one line per hand that came up, best first, then jacks or better and the total
+impl std::fmt::Display for Frequencies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |count: u64| count as f64 / self.total as f64 * 100.0;
+
+        for (ranking, &count) in self.counts.iter().rev() {
+            writeln!(
+                f,
+                "{:<16} {:>10} {:>10.4}%",
+                ranking.to_string(),
+                count,
+                percent(count)
+            )?;
+        }
+
+        writeln!(
+            f,
+            "{:<16} {:>10} {:>10.4}%",
+            "Jacks or Better",
+            self.jacks_or_better,
+            percent(self.jacks_or_better)
+        )?;
+
+        write!(f, "{:<16} {:>10}", "Total", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    #[test]
+    fn exhaustive_counts() {
+        use HandRanking::*;
+
+        let frequencies = Frequencies::exhaustive();
+
+        assert_eq!(frequencies.total(), 2_598_960);
+        assert_eq!(frequencies.count(RoyalFlush), 4);
+        assert_eq!(frequencies.count(StraightFlush), 36);
+        assert_eq!(frequencies.count(FourOfAKind), 624);
+        assert_eq!(frequencies.count(FullHouse), 3_744);
+        assert_eq!(frequencies.count(Flush), 5_108);
+        assert_eq!(frequencies.count(Straight), 10_200);
+        assert_eq!(frequencies.count(ThreeOfAKind), 54_912);
+        assert_eq!(frequencies.count(TwoPair), 123_552);
+        assert_eq!(frequencies.count(Pair), 1_098_240);
+        assert_eq!(frequencies.count(HighCard), 1_302_540);
+        assert_eq!(frequencies.count(FiveOfAKind), 0);
+        assert_eq!(frequencies.jacks_or_better(), 536_100);
+    }
+
+    #[test]
+    fn report() {
+        let mut frequencies = Frequencies::new();
+        for hand in [
+            "Jh Jd 2c 9s Ah",
+            "Th Td 2c 9s Ah",
+            "Kh 7c Ks 7d Ah",
+            "Qh 9d 7c 4s 3h",
+        ] {
+            frequencies.add(&Hand::new(&parse_cards(hand).unwrap()));
+        }
+
+        assert_eq!(frequencies.probability(HandRanking::Pair), 0.5);
+        assert_eq!(frequencies.jacks_or_better(), 2);
+        assert_eq!(
+            frequencies.to_string(),
+            [
+                "Two Pair                  1    25.0000%",
+                "Pair                      2    50.0000%",
+                "High Card                 1    25.0000%",
+                "Jacks or Better           2    50.0000%",
+                "Total                     4",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,32 +1,30 @@
 use crate::card;
+use crate::score::PayLine;
 
-use enum_iterator::Sequence;
-
-#[derive(Sequence)]
+#[derive(Clone, Copy)]
 enum HandRankingElement {
     Two = 0b1,
     Pair = 0b10,
     Four = 0b100,
-    FullHouse = 0b1000,
-    Royal = 0b1_0000,
-    Three = 0b10_0000,
-    OfAKind = 0b100_0000,
-    Straight = 0b1000_0000,
-    Flush = 0b1_0000_0000,
-    Empty = 0b10_0000_0000,
+    Deuces = 0b1000,
+    FullHouse = 0b1_0000,
+    Wild = 0b10_0000,
+    Royal = 0b100_0000,
+    Three = 0b1000_0000,
+    Five = 0b1_0000_0000,
+    OfAKind = 0b10_0000_0000,
+    Straight = 0b100_0000_0000,
+    Flush = 0b1000_0000_0000,
 }
 
-/*
 use HandRankingElement::*;
-const DISPLAY_ROWS: [[HandRankingElement; 5]; 2] = [
-    [Two, Pair, Four, FullHouse, Royal],
-    [Three, OfAKind, Straight, Flush, Empty],
+const DISPLAY_ROWS: [&[HandRankingElement]; 2] = [
+    &[Two, Pair, Four, Deuces, FullHouse, Wild, Royal],
+    &[Three, Five, OfAKind, Straight, Flush],
 ];
-*/
 
 impl std::fmt::Display for HandRankingElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use HandRankingElement::*;
         write!(
             f,
             "{}",
@@ -34,21 +32,28 @@ impl std::fmt::Display for HandRankingElement {
                 Two => "TWO",
                 Pair => "PAIR",
                 Four => "FOUR",
+                Deuces => "DEUCES",
                 FullHouse => "FULL HOUSE",
+                Wild => "WILD",
                 Royal => "ROYAL",
                 Three => "THREE",
+                Five => "FIVE",
                 OfAKind => "of a KIND",
                 Straight => "STRAIGHT",
                 Flush => "FLUSH",
-                Empty => "",
             }
         )
     }
 }
 
-fn hand_ranking_to_display_bitmap(ranking: &card::HandRanking) -> u16 {
+fn pay_line_to_display_bitmap(line: &PayLine) -> u16 {
     use card::HandRanking;
-    use HandRankingElement::*;
+
+    let ranking = match line {
+        PayLine::Hand(ranking) => ranking,
+        PayLine::WildRoyalFlush => return Wild as u16 | Royal as u16,
+        PayLine::FourDeuces => return Four as u16 | Deuces as u16,
+    };
 
     match ranking {
         HandRanking::HighCard => 0,
@@ -61,44 +66,40 @@ fn hand_ranking_to_display_bitmap(ranking: &card::HandRanking) -> u16 {
         HandRanking::FourOfAKind => Four as u16 | OfAKind as u16,
         HandRanking::StraightFlush => Straight as u16 | Flush as u16,
         HandRanking::RoyalFlush => Royal as u16 | Flush as u16,
-        HandRanking::FiveOfAKind => 0,
+        HandRanking::FiveOfAKind => Five as u16 | OfAKind as u16,
+        HandRanking::FlushFive => Five as u16 | OfAKind as u16 | Flush as u16,
     }
 }
 
-pub struct HandRankingDisplay(pub Option<card::HandRanking>);
+pub struct HandRankingDisplay(pub Option<PayLine>);
 
 impl std::fmt::Display for HandRankingDisplay {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let blank_style = console::Style::new().dim();
         let lit_style = console::Style::new();
 
-        for (i, element) in enum_iterator::all::<HandRankingElement>().enumerate() {
-            let label = format!("{}", element);
-
-            let blank = blank_style.apply_to(".".repeat(label.len()));
-
-            let label = match &self.0 {
-                Some(ranking) => {
-                    let bitmap = hand_ranking_to_display_bitmap(ranking);
-
-                    if bitmap >> i & 1 == 1 {
-                        lit_style.apply_to(label)
-                    } else {
-                        blank
-                    }
-                }
-                None => blank,
-            };
+        let bitmap = self.0.as_ref().map(pay_line_to_display_bitmap).unwrap_or(0);
 
+        for (row_index, row) in DISPLAY_ROWS.iter().enumerate() {
             // end of the first line
-            if i == 5 {
-                write!(f, "\n")?;
+            if row_index > 0 {
+                writeln!(f)?;
             }
 
-            if i == 0 || i == 5 || i == 9 {
-                write!(f, "{}", label)?;
-            } else {
-                write!(f, " {}", label)?;
+            for (i, &element) in row.iter().enumerate() {
+                let label = format!("{}", element);
+
+                let label = if bitmap & element as u16 != 0 {
+                    lit_style.apply_to(label)
+                } else {
+                    blank_style.apply_to(".".repeat(label.len()))
+                };
+
+                if i == 0 {
+                    write!(f, "{}", label)?;
+                } else {
+                    write!(f, " {}", label)?;
+                }
             }
         }
         Ok(())